@@ -0,0 +1,101 @@
+//! Derive macros for [anybytes](https://docs.rs/anybytes).
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Type};
+
+/// Derives `ByteSource` for a struct wrapping a single `ByteSource` field.
+///
+/// The wrapper becomes its own owner and exposes the bytes of the wrapped
+/// field. Additional `PhantomData` marker fields are allowed.
+///
+/// ```
+/// #[derive(anybytes::ByteSource)]
+/// struct Blob(Vec<u8>);
+///
+/// let bytes = anybytes::Bytes::from_source(Blob(vec![1, 2, 3]));
+/// assert_eq!(bytes, [1, 2, 3]);
+/// ```
+#[proc_macro_derive(ByteSource)]
+pub fn derive_byte_source(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "ByteSource can only be derived for structs",
+            ))
+        }
+    };
+
+    let members: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !is_phantom(&field.ty))
+        .collect();
+    let (index, field) =
+        match members.as_slice() {
+            [single] => *single,
+            _ => return Err(Error::new_spanned(
+                fields,
+                "ByteSource can only be derived for structs with exactly one non-PhantomData field",
+            )),
+        };
+    let member = match fields {
+        Fields::Named(_) => {
+            let ident = field.ident.as_ref().expect("named field");
+            quote!(#ident)
+        }
+        _ => {
+            let index = syn::Index::from(index);
+            quote!(#index)
+        }
+    };
+
+    let name = &input.ident;
+    let ty = &field.ty;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut predicates = where_clause
+        .map(|clause| clause.predicates.clone())
+        .unwrap_or_default();
+    predicates.push(syn::parse_quote!(#ty: ::anybytes::ByteSource));
+    predicates.push(syn::parse_quote!(Self: ::core::marker::Sync + ::core::marker::Send + 'static));
+
+    Ok(quote! {
+        // The wrapped field is a `ByteSource`, so its bytes stay valid
+        // while it is moved, and the wrapper keeps it alive as the owner.
+        unsafe impl #impl_generics ::anybytes::ByteSource for #name #ty_generics
+        where
+            #predicates
+        {
+            type Owner = Self;
+
+            fn as_bytes(&self) -> &[u8] {
+                ::anybytes::ByteSource::as_bytes(&self.#member)
+            }
+
+            fn as_owner(self) -> Self::Owner {
+                self
+            }
+        }
+    })
+}
+
+fn is_phantom(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
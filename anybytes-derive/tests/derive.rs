@@ -0,0 +1,35 @@
+use std::marker::PhantomData;
+
+use anybytes::{ByteSource, Bytes};
+
+#[derive(ByteSource)]
+struct Blob(Vec<u8>);
+
+#[derive(ByteSource)]
+struct Tagged<T: Send + Sync + 'static> {
+    text: String,
+    _tag: PhantomData<T>,
+}
+
+#[test]
+fn tuple_newtype() {
+    let bytes = Bytes::from_source(Blob(vec![1, 2, 3]));
+    assert_eq!(bytes, [1, 2, 3]);
+    assert!(bytes.downcast_to_owner::<Blob>().is_some());
+}
+
+#[test]
+fn named_with_marker() {
+    let tagged: Tagged<u32> = Tagged {
+        text: "hello".to_string(),
+        _tag: PhantomData,
+    };
+    let bytes = Bytes::from_source(tagged);
+    assert_eq!(bytes, b"hello");
+}
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
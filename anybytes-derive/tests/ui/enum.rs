@@ -0,0 +1,7 @@
+#[derive(anybytes::ByteSource)]
+enum Either {
+    A(Vec<u8>),
+    B(String),
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(anybytes::ByteSource)]
+struct Pair {
+    a: Vec<u8>,
+    b: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,70 @@
+//! Measures `Bytes::clone`/`slice` throughput when many threads share one
+//! owner, compared to `slice_cached` and borrowing with `slice_borrowed`.
+//!
+//! Run with `cargo bench --bench clone`.
+
+use std::hint::black_box;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use std::sync::OnceLock;
+
+use anybytes::{Bytes, SliceCache};
+
+const ITERATIONS: usize = 1_000_000;
+
+fn run(threads: usize, bytes: &Bytes, op: fn(&Bytes, usize) -> usize) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                let mut acc = 0;
+                for i in 0..ITERATIONS {
+                    acc += op(bytes, i);
+                }
+                black_box(acc);
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn clone(bytes: &Bytes, _: usize) -> usize {
+    black_box(bytes.clone()).len()
+}
+
+fn slice(bytes: &Bytes, i: usize) -> usize {
+    let start = i % bytes.len();
+    black_box(bytes.slice(start..)).len()
+}
+
+fn slice_cached(bytes: &Bytes, i: usize) -> usize {
+    static CACHE: OnceLock<SliceCache> = OnceLock::new();
+    let cache = CACHE.get_or_init(SliceCache::new);
+    let start = i % 64;
+    black_box(bytes.slice_cached(start..bytes.len(), cache)).len()
+}
+
+fn slice_borrowed(bytes: &Bytes, i: usize) -> usize {
+    let start = i % bytes.len();
+    black_box(bytes.slice_borrowed(start..)).len()
+}
+
+fn main() {
+    let bytes = Bytes::from(vec![0u8; 4096]);
+    let max_threads = thread::available_parallelism().map_or(4, |n| n.get());
+    let mut threads = 1;
+    while threads <= max_threads {
+        for (name, op) in [
+            ("clone", clone as fn(&Bytes, usize) -> usize),
+            ("slice", slice),
+            ("slice_cached", slice_cached),
+            ("slice_borrowed", slice_borrowed),
+        ] {
+            let elapsed = run(threads, &bytes, op);
+            let per_op = elapsed.as_nanos() as f64 / ITERATIONS as f64;
+            println!("{name:>14} threads={threads:<3} {per_op:>8.2} ns/op per thread");
+        }
+        threads *= 2;
+    }
+}
@@ -0,0 +1,64 @@
+//! [arbitrary](https://docs.rs/arbitrary) support for fuzzing with [Bytes].
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::Bytes;
+
+impl<'a> Arbitrary<'a> for Bytes {
+    /// Generates owned [Bytes] that sometimes reference only a
+    /// subrange of their owner, to exercise the slicing paths.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // The trim amounts are drawn first, as the vector consumes
+        // whatever input remains.
+        let trim: Option<(u8, u8)> = Arbitrary::arbitrary(u)?;
+        let bytes = Bytes::from_source(Vec::<u8>::arbitrary(u)?);
+        let Some((front, back)) = trim else {
+            return Ok(bytes);
+        };
+        let start = front as usize % (bytes.len() + 1);
+        let end = bytes.len() - back as usize % (bytes.len() - start + 1);
+        Ok(bytes.slice(start..end))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        ::arbitrary::size_hint::and(
+            <Option<(u8, u8)> as Arbitrary>::size_hint(depth),
+            <Vec<u8> as Arbitrary>::size_hint(depth),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::arbitrary::{Arbitrary, Unstructured};
+
+    use crate::Bytes;
+
+    #[test]
+    fn smoke() {
+        let mut sliced = false;
+        let mut empty = false;
+        for seed in 0..64u8 {
+            let mut state = seed as u32;
+            let raw: Vec<u8> = (0..256)
+                .map(|_| {
+                    state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                    (state >> 16) as u8
+                })
+                .collect();
+            let mut u = Unstructured::new(&raw);
+            for _ in 0..8 {
+                let b = Bytes::arbitrary(&mut u).unwrap();
+                b.validate_invariants();
+                let full = b.clone().downcast_to_owner::<Vec<u8>>().unwrap();
+                sliced |= full.len() != b.len();
+                empty |= b.is_empty();
+            }
+        }
+        assert!(sliced);
+        assert!(empty);
+
+        let b = Bytes::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert!(b.is_empty());
+    }
+}
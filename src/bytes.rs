@@ -6,14 +6,25 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+#[cfg(feature = "mmap")]
+use crate::owners::MappedFile;
+
 use std::any::Any;
 use std::ascii::escape_default;
 use std::borrow;
 use std::cmp;
+use std::collections::VecDeque;
+use std::ffi::CStr;
 use std::fmt;
+#[cfg(feature = "mmap")]
+use std::fs::File;
 use std::hash;
+use std::io;
 use std::ops;
+use std::ops::Range;
+use std::path::Path;
 use std::slice::SliceIndex;
+use std::str::Utf8Error;
 use std::sync::Arc;
 use std::sync::Weak;
 
@@ -25,24 +36,90 @@ fn is_subslice(slice: &[u8], subslice: &[u8]) -> bool {
     subslice_start >= slice_start && subslice_end <= slice_end
 }
 
-unsafe fn erase_lifetime<'a>(slice: &'a [u8]) -> &'static [u8] {
+#[cfg(feature = "mmap")]
+fn mmap_unavailable(err: &io::Error) -> bool {
+    #[cfg(unix)]
+    if err.raw_os_error() == Some(libc::ENODEV) {
+        return true;
+    }
+    matches!(
+        err.kind(),
+        io::ErrorKind::PermissionDenied | io::ErrorKind::Unsupported
+    )
+}
+
+pub(crate) unsafe fn erase_lifetime(slice: &[u8]) -> &'static [u8] {
     &*(slice as *const [u8])
 }
 
+/// A type that can provide the bytes backing a [Bytes].
+///
+/// # Safety
+/// The slice returned by [ByteSource::as_bytes] must remain valid
+/// and unchanged for as long as the owner returned by
+/// [ByteSource::as_owner] is alive, even if the owner is moved.
 pub unsafe trait ByteSource {
     type Owner: ByteOwner;
 
     fn as_bytes(&self) -> &[u8];
+    #[allow(clippy::wrong_self_convention)]
     fn as_owner(self) -> Self::Owner;
 }
 pub trait ByteOwner: Sync + Send + 'static {
     fn as_any(self: Arc<Self>) -> Arc<dyn Any + Sync + Send>;
+
+    /// Returns the full extent of the memory held by this owner, if known.
+    ///
+    /// Used by [Bytes::validate_invariants] to check that a [Bytes]
+    /// only references memory kept alive by its owner.
+    fn owner_bytes(&self) -> Option<&[u8]> {
+        None
+    }
 }
 
+/// A [ByteSource] whose bytes are always valid UTF-8.
+///
+/// This allows text types like [PackedStr](crate::PackedStr) to be
+/// created without revalidating the data.
+///
+/// # Safety
+/// [ByteSource::as_bytes] must always return valid UTF-8.
+pub unsafe trait TextOwner: ByteSource {}
+
 impl<T: ByteSource + Sync + Send + 'static> ByteOwner for T {
     fn as_any(self: Arc<Self>) -> Arc<dyn Any + Sync + Send> {
         self
     }
+
+    fn owner_bytes(&self) -> Option<&[u8]> {
+        Some(ByteSource::as_bytes(self))
+    }
+}
+
+/// Access pattern hints for [Bytes::advise].
+#[cfg(all(feature = "mmap", unix))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Advice {
+    /// No special treatment, the default.
+    Normal,
+    /// Pages will be accessed in order, so read-ahead can be aggressive.
+    Sequential,
+    /// Pages will be accessed in random order, so read-ahead is wasted.
+    Random,
+    /// Pages will be needed soon and should be read in ahead of time.
+    WillNeed,
+    /// Pages won't be needed soon and can be dropped from memory.
+    DontNeed,
+}
+
+/// Error returned by [Bytes::copy_to_slice] and [Bytes::copy_to_slice_exact]
+/// when the destination length doesn't fit the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyError {
+    /// Number of bytes in the source `Bytes`.
+    pub src_len: usize,
+    /// Number of bytes in the destination slice.
+    pub dst_len: usize,
 }
 
 /// Immutable bytes with zero-copy slicing and cloning.
@@ -136,11 +213,52 @@ impl Bytes {
         }
     }
 
+    /// Decomposes the Bytes into a data pointer, a length and the owner.
+    ///
+    /// This allows moving Bytes across boundaries that can only transport
+    /// raw parts, see [Bytes::from_parts] for reassembly.
+    pub fn into_parts(self) -> (*const u8, usize, Arc<dyn ByteOwner>) {
+        (self.data.as_ptr(), self.data.len(), self.owner)
+    }
+
+    /// Reassembles Bytes from the parts returned by [Bytes::into_parts].
+    ///
+    /// # Safety
+    /// `ptr` and `len` must describe memory that is valid for reads and
+    /// kept alive and unchanged by `owner`, such as the parts of a
+    /// single [Bytes::into_parts] call.
+    pub unsafe fn from_parts(ptr: *const u8, len: usize, owner: Arc<dyn ByteOwner>) -> Self {
+        let data = std::slice::from_raw_parts(ptr, len);
+        Self {
+            data: erase_lifetime(data),
+            owner,
+        }
+    }
+
+    /// Returns the data as a byte slice.
+    ///
+    /// This is equivalent to `Deref` and `AsRef<[u8]>`, but unambiguous
+    /// in generic code with multiple `AsRef` bounds in scope.
     #[inline]
-    pub(crate) fn as_slice<'a>(&'a self) -> &'a [u8] {
+    pub fn as_slice(&self) -> &[u8] {
         self.data
     }
 
+    /// Returns the data as a `&str` if it is valid UTF-8.
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        std::str::from_utf8(self.as_slice())
+    }
+
+    /// Copies the data into a new heap allocation that isn't shared
+    /// with the current owner.
+    ///
+    /// Unlike `clone`, this allows the original owner (e.g. a memory
+    /// map or a Python object) to be released.
+    pub fn make_owned(&self) -> Self {
+        let copy: Box<[u8]> = self.as_slice().into();
+        Self::from_source(copy)
+    }
+
     /// Returns the owner of the Bytes as a `Arc<T>`.
     ///
     /// # Examples
@@ -152,17 +270,64 @@ impl Bytes {
     /// let bytes = Bytes::from_source(owner);
     /// let owner: Arc<Vec<u8>> = bytes.downcast_to_owner().expect("Downcast of known type.");
     /// ```
+    ///
+    /// Data mapped by the `Bytes::map_*` constructors is owned by
+    /// a `memmap2::Mmap`.
     pub fn downcast_to_owner<T>(self) -> Option<Arc<T>>
     where
         T: Send + Sync + 'static,
     {
         let owner = self.owner;
         let owner = ByteOwner::as_any(owner);
+        #[cfg(feature = "mmap")]
+        let owner = match owner.downcast::<MappedFile>() {
+            Ok(mapped) => mapped.mmap.clone() as Arc<dyn Any + Sync + Send>,
+            Err(owner) => owner,
+        };
         owner.downcast::<T>().ok()
     }
 
+    /// Returns the owner of the Bytes by value if it is of type `T`
+    /// and no other [Bytes] share it.
+    ///
+    /// Otherwise `self` is returned unchanged.
+    pub fn try_unwrap_owner<T>(self) -> Result<T, Self>
+    where
+        T: ByteOwner,
+    {
+        let data = self.data;
+        let owner = ByteOwner::as_any(self.owner.clone());
+        let Ok(owner) = owner.downcast::<T>() else {
+            return Err(self);
+        };
+        drop(self);
+        Arc::try_unwrap(owner).map_err(|owner| Self { data, owner })
+    }
+
+    /// Converts the Bytes into a `Vec<u8>`.
+    ///
+    /// If the Bytes are the sole owner of a `Vec<u8>` and span all of it,
+    /// the vector is returned without copying. Otherwise the data is copied.
+    pub fn into_vec(self) -> Vec<u8> {
+        let spans_owner = self
+            .owner
+            .owner_bytes()
+            .is_some_and(|extent| std::ptr::eq(extent, self.data));
+        if !spans_owner {
+            return self.as_slice().to_vec();
+        }
+        match self.try_unwrap_owner::<Vec<u8>>() {
+            Ok(vec) => vec,
+            Err(bytes) => bytes.as_slice().to_vec(),
+        }
+    }
+
     /// Returns a slice of self for the provided range.
     /// This operation is `O(1)`.
+    ///
+    /// Like `clone`, this increments the owner's atomic reference count,
+    /// which can become contended when many threads slice the same owner.
+    /// Use [Bytes::slice_borrowed] for transient access.
     pub fn slice(&self, range: impl SliceIndex<[u8], Output = [u8]>) -> Self {
         Self {
             data: &self.data[range],
@@ -170,6 +335,13 @@ impl Bytes {
         }
     }
 
+    /// Returns a borrowed slice for the provided range without touching
+    /// the owner's reference count.
+    #[inline]
+    pub fn slice_borrowed(&self, range: impl SliceIndex<[u8], Output = [u8]>) -> &[u8] {
+        &self.as_slice()[range]
+    }
+
     /// Attempt to convert `slice` to a zero-copy slice of this `Bytes`.
     ///
     /// Returns `None` if `slice` is outside the memory range of this
@@ -187,6 +359,60 @@ impl Bytes {
         }
     }
 
+    /// Removes and returns the first `len` bytes.
+    ///
+    /// Returns `None` and leaves `self` unchanged if fewer than
+    /// `len` bytes are available.
+    pub fn take_prefix(&mut self, len: usize) -> Option<Self> {
+        if len > self.len() {
+            return None;
+        }
+        let (data, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(Self {
+            data,
+            owner: self.owner.clone(),
+        })
+    }
+
+    /// Returns the data as an [io::IoSlice] for vectored writes.
+    pub fn as_io_slice(&self) -> io::IoSlice<'_> {
+        io::IoSlice::new(self.as_slice())
+    }
+
+    /// Returns `true` if both Bytes reference the same memory range.
+    pub fn ptr_eq(&self, other: &Bytes) -> bool {
+        std::ptr::eq(self.data, other.data)
+    }
+
+    /// Returns `true` if both Bytes share the same owner.
+    pub fn owner_ptr_eq(&self, other: &Bytes) -> bool {
+        Arc::ptr_eq(&self.owner, &other.owner)
+    }
+
+    /// Returns how many bytes `self` starts after `start`.
+    ///
+    /// Returns `None` if `start` doesn't share the owner with `self` or
+    /// `self` doesn't lie within the memory range of `start`, e.g. when
+    /// the two are unrelated or `start` is not an earlier checkpoint.
+    pub fn checked_offset_from(&self, start: &Bytes) -> Option<usize> {
+        if self.owner_ptr_eq(start) && is_subslice(start.data, self.data) {
+            Some(self.data.as_ptr() as usize - start.data.as_ptr() as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of [Bytes] sharing this owner.
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.owner)
+    }
+
+    /// Returns the number of [WeakBytes] pointing to this owner.
+    pub fn weak_count(&self) -> usize {
+        Arc::weak_count(&self.owner)
+    }
+
     /// Create a weak pointer.
     pub fn downgrade(&self) -> WeakBytes {
         WeakBytes {
@@ -194,9 +420,888 @@ impl Bytes {
             owner: Arc::downgrade(&self.owner),
         }
     }
+
+    /// Copies the data into the front of `dst`.
+    ///
+    /// Fails if `dst` is shorter than `self`.
+    pub fn copy_to_slice(&self, dst: &mut [u8]) -> Result<(), CopyError> {
+        if dst.len() < self.len() {
+            return Err(CopyError {
+                src_len: self.len(),
+                dst_len: dst.len(),
+            });
+        }
+        dst[..self.len()].copy_from_slice(self.as_slice());
+        Ok(())
+    }
+
+    /// Copies the data into `dst`, which must have exactly the same length.
+    pub fn copy_to_slice_exact(&self, dst: &mut [u8]) -> Result<(), CopyError> {
+        if dst.len() != self.len() {
+            return Err(CopyError {
+                src_len: self.len(),
+                dst_len: dst.len(),
+            });
+        }
+        dst.copy_from_slice(self.as_slice());
+        Ok(())
+    }
+
+    /// Reads `reader` to the end and returns its contents as `Bytes`.
+    pub fn from_reader<R: io::Read>(reader: R) -> io::Result<Self> {
+        Self::from_reader_with_capacity(reader, 0)
+    }
+
+    /// Like [Bytes::from_reader] but preallocates `capacity` bytes.
+    pub fn from_reader_with_capacity<R: io::Read>(
+        mut reader: R,
+        capacity: usize,
+    ) -> io::Result<Self> {
+        let mut v = Vec::with_capacity(capacity);
+        reader.read_to_end(&mut v)?;
+        Ok(Self::from_source(v))
+    }
+
+    /// Reads the whole file at `path` into memory and returns it as `Bytes`.
+    ///
+    /// Unlike [Bytes::map_file] this copies the data, but it doesn't
+    /// require the `mmap` feature and is safe for files that change later.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let v = std::fs::read(path)?;
+        Ok(Self::from_source(v))
+    }
+
+    /// Reads exactly `len` bytes at `offset` from `file` without
+    /// moving its cursor or memory mapping it.
+    ///
+    /// This is well suited for sparse random access into large files.
+    #[cfg(any(unix, windows))]
+    pub fn pread(file: &std::fs::File, offset: u64, len: usize) -> io::Result<Self> {
+        let mut buf = vec![0u8; len];
+        #[cfg(unix)]
+        std::os::unix::fs::FileExt::read_exact_at(file, &mut buf, offset)?;
+        #[cfg(windows)]
+        {
+            let mut filled = 0;
+            while filled < len {
+                let n = std::os::windows::fs::FileExt::seek_read(
+                    file,
+                    &mut buf[filled..],
+                    offset + filled as u64,
+                )?;
+                if n == 0 {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                filled += n;
+            }
+        }
+        Ok(Self::from_source(buf))
+    }
+
+    /// Memory maps `file` read-only and returns its contents as `Bytes`.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while the mapping
+    /// is alive, as this would change or invalidate the returned data.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map_file(file: &File) -> io::Result<Self> {
+        let mmap = memmap2::Mmap::map(file)?;
+        Ok(Self::from_source(MappedFile::new(mmap, None)))
+    }
+
+    /// Memory maps `len` bytes of `file` starting at `offset`.
+    ///
+    /// Mappings start at page boundaries, so the returned data begins
+    /// `offset % page_size` bytes past a page-aligned address. Its alignment
+    /// is therefore determined by `offset` alone, see [Bytes::region_alignment].
+    /// In particular, a `T` at `offset` can only be viewed in place if
+    /// `offset` is a multiple of `align_of::<T>()`.
+    ///
+    /// # Safety
+    /// See [Bytes::map_file].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map_file_region(file: &File, offset: u64, len: usize) -> io::Result<Self> {
+        let mmap = memmap2::MmapOptions::new()
+            .offset(offset)
+            .len(len)
+            .map(file)?;
+        Ok(Self::from_source(MappedFile::new(mmap, None)))
+    }
+
+    /// Returns the alignment guaranteed for the data of a region mapped at
+    /// `file_offset` via [Bytes::map_file_region] or [Bytes::map_path_region].
+    ///
+    /// This is the largest power of two dividing `file_offset`, capped at
+    /// 4096, the smallest page size of supported platforms.
+    #[cfg(feature = "mmap")]
+    pub fn region_alignment(file_offset: u64) -> usize {
+        const MIN_PAGE_SIZE: usize = 4096;
+        if file_offset == 0 {
+            return MIN_PAGE_SIZE;
+        }
+        (1usize << file_offset.trailing_zeros().min(12)).min(MIN_PAGE_SIZE)
+    }
+
+    /// Opens the file at `path` read-only and memory maps it.
+    ///
+    /// The file handle is kept open for as long as the mapping is alive.
+    ///
+    /// # Safety
+    /// See [Bytes::map_file].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let mmap = memmap2::Mmap::map(&file)?;
+        Ok(Self::from_source(MappedFile::new(mmap, Some(file))))
+    }
+
+    /// Opens the file at `path` read-only and memory maps `len` bytes
+    /// starting at `offset`.
+    ///
+    /// # Safety
+    /// See [Bytes::map_file].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map_path_region<P: AsRef<Path>>(
+        path: P,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let mmap = memmap2::MmapOptions::new()
+            .offset(offset)
+            .len(len)
+            .map(&file)?;
+        Ok(Self::from_source(MappedFile::new(mmap, Some(file))))
+    }
+
+    /// Returns `true` if the data is backed by a memory map.
+    #[cfg(feature = "mmap")]
+    pub fn is_mapped(&self) -> bool {
+        let owner = ByteOwner::as_any(self.owner.clone());
+        owner.is::<MappedFile>() || owner.is::<memmap2::Mmap>()
+    }
+
+    /// Returns the number of pages of the data that are resident
+    /// in memory, together with the total number of pages spanned.
+    ///
+    /// Fails with [io::ErrorKind::Unsupported] if the data isn't backed
+    /// by a memory map.
+    #[cfg(all(feature = "mmap", unix))]
+    pub fn resident_pages(&self) -> io::Result<(usize, usize)> {
+        if !self.is_mapped() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "bytes are not memory mapped",
+            ));
+        }
+        if self.data.is_empty() {
+            return Ok((0, 0));
+        }
+        let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let start = self.data.as_ptr() as usize;
+        let aligned_start = start - start % page;
+        let len = start + self.data.len() - aligned_start;
+        let total = len.div_ceil(page);
+        let mut pages = vec![0u8; total];
+        let ret = unsafe {
+            libc::mincore(
+                aligned_start as *mut libc::c_void,
+                len,
+                pages.as_mut_ptr() as *mut _,
+            )
+        };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let resident = pages.iter().filter(|&&page| page & 1 != 0).count();
+        Ok((resident, total))
+    }
+
+    // Runs `f` with the memory map backing the data, the offset of the
+    // data within it and whether the map is known to be file backed.
+    // Returns `None` if the data isn't mapped.
+    #[cfg(all(feature = "mmap", unix))]
+    fn with_mmap<R>(&self, f: impl FnOnce(&memmap2::Mmap, usize, bool) -> R) -> Option<R> {
+        let owner = ByteOwner::as_any(self.owner.clone());
+        let (mmap, file_backed) = match owner.downcast_ref::<MappedFile>() {
+            Some(mapped) => (&*mapped.mmap, true),
+            None => (owner.downcast_ref::<memmap2::Mmap>()?, false),
+        };
+        let offset = self.data.as_ptr() as usize - mmap.as_ptr() as usize;
+        Some(f(mmap, offset, file_backed))
+    }
+
+    /// Advises the kernel how the data will be accessed, so it can
+    /// tune read-ahead and caching of the pages.
+    ///
+    /// This is a no-op for data that isn't backed by a memory map.
+    /// [Advice::DontNeed] is also ignored for maps not created by this
+    /// crate, as dropping anonymous pages would discard their contents.
+    #[cfg(all(feature = "mmap", unix))]
+    pub fn advise(&self, advice: Advice) -> io::Result<()> {
+        if self.data.is_empty() {
+            return Ok(());
+        }
+        let len = self.data.len();
+        self.with_mmap(|mmap, offset, file_backed| match advice {
+            Advice::Normal => mmap.advise_range(memmap2::Advice::Normal, offset, len),
+            Advice::Sequential => mmap.advise_range(memmap2::Advice::Sequential, offset, len),
+            Advice::Random => mmap.advise_range(memmap2::Advice::Random, offset, len),
+            Advice::WillNeed => mmap.advise_range(memmap2::Advice::WillNeed, offset, len),
+            // The file mapping is read-only, so dropped pages are simply
+            // read from the file again on the next access.
+            Advice::DontNeed if file_backed => unsafe {
+                mmap.unchecked_advise_range(memmap2::UncheckedAdvice::DontNeed, offset, len)
+            },
+            Advice::DontNeed => Ok(()),
+        })
+        .unwrap_or(Ok(()))
+    }
+
+    /// Locks the pages spanned by the data into memory, so they are
+    /// never swapped out, e.g. for key material.
+    ///
+    /// Locking works on whole pages and isn't reference counted: when
+    /// two slices share a page, unlocking either of them also unlocks
+    /// that page for the other one.
+    ///
+    /// Fails with [io::ErrorKind::Unsupported] if the data isn't backed
+    /// by a memory map.
+    #[cfg(all(feature = "mmap", unix))]
+    pub fn lock(&self) -> io::Result<()> {
+        self.mlock_with(libc::mlock)
+    }
+
+    /// Undoes [Bytes::lock], allowing the pages to be swapped out again.
+    ///
+    /// This unlocks every page spanned by the data, including pages
+    /// shared with other locked slices, see [Bytes::lock].
+    #[cfg(all(feature = "mmap", unix))]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.mlock_with(libc::munlock)
+    }
+
+    #[cfg(all(feature = "mmap", unix))]
+    fn mlock_with(
+        &self,
+        f: unsafe extern "C" fn(*const libc::c_void, libc::size_t) -> libc::c_int,
+    ) -> io::Result<()> {
+        if !self.is_mapped() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "bytes are not memory mapped",
+            ));
+        }
+        if self.data.is_empty() {
+            return Ok(());
+        }
+        let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let start = self.data.as_ptr() as usize;
+        let aligned_start = start - start % page;
+        let len = start + self.data.len() - aligned_start;
+        if unsafe { f(aligned_start as *const libc::c_void, len) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Loads the file at `path` the best way available.
+    ///
+    /// The file is memory mapped, but if the environment doesn't allow
+    /// mapping it (e.g. under seccomp or for special files) its contents
+    /// are read into memory instead.
+    ///
+    /// # Safety
+    /// See [Bytes::map_file].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn load_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        match Self::map_file(&file) {
+            Ok(bytes) => Ok(bytes),
+            Err(err) if mmap_unavailable(&err) => {
+                let mut v = Vec::new();
+                io::Read::read_to_end(&mut file, &mut v)?;
+                Ok(Self::from_source(v))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Decompresses zstd compressed data into new `Bytes`.
+    #[cfg(feature = "zstd")]
+    pub fn decompress_zstd(&self) -> io::Result<Self> {
+        let decoder = zstd::stream::read::Decoder::with_buffer(self.as_slice())?;
+        Self::from_reader(decoder)
+    }
+
+    /// Decompresses gzip compressed data into new `Bytes`.
+    #[cfg(feature = "flate2")]
+    pub fn decompress_gzip(&self) -> io::Result<Self> {
+        let decoder = flate2::read::GzDecoder::new(self.as_slice());
+        Self::from_reader(decoder)
+    }
+
+    /// Computes the CRC-32 (IEEE) checksum of the referenced data.
+    #[cfg(feature = "crc32")]
+    pub fn crc32(&self) -> u32 {
+        crc32fast::hash(self.as_slice())
+    }
+
+    /// Hashes the referenced data with the [digest::Digest] `D`.
+    ///
+    /// The data is hashed in place, so memory mapped `Bytes` are not
+    /// copied first.
+    #[cfg(feature = "digest")]
+    pub fn digest<D: digest::Digest>(&self) -> digest::Output<D> {
+        D::digest(self.as_slice())
+    }
+
+    /// Removes up to `n` bytes from the front of `deque` and returns them
+    /// as `Bytes`.
+    ///
+    /// The bytes are copied into a fresh allocation, so this works
+    /// regardless of whether the deque's storage is contiguous.
+    pub fn drain_front_of_deque(deque: &mut VecDeque<u8>, n: usize) -> Self {
+        let n = n.min(deque.len());
+        let v: Vec<u8> = deque.drain(..n).collect();
+        Self::from_source(v)
+    }
+
+    /// Checks that the referenced data lies within the memory reported by
+    /// the owner via [ByteOwner::owner_bytes].
+    ///
+    /// This is a debugging aid for custom [ByteSource] implementations and
+    /// only does work with `debug_assertions` or the `validate` feature.
+    ///
+    /// # Panics
+    /// Panics with a description of both ranges if the data lies
+    /// outside of the owner's memory.
+    #[inline]
+    pub fn validate_invariants(&self) {
+        #[cfg(any(debug_assertions, feature = "validate"))]
+        if let Some(extent) = self.owner.owner_bytes() {
+            if !self.data.is_empty() && !is_subslice(extent, self.data) {
+                panic!(
+                    "Bytes data {:p}+{} lies outside of its owner's memory {:p}+{}",
+                    self.data.as_ptr(),
+                    self.data.len(),
+                    extent.as_ptr(),
+                    extent.len(),
+                );
+            }
+        }
+    }
+
+    /// Returns `Bytes` that are guaranteed to end in a nul byte.
+    ///
+    /// If the data already ends in `\0` this is a zero-copy clone,
+    /// otherwise the data is copied into a new allocation with a
+    /// trailing nul appended.
+    pub fn with_nul_terminator(&self) -> Self {
+        if self.as_slice().last() == Some(&0) {
+            return self.clone();
+        }
+        let mut v = Vec::with_capacity(self.len() + 1);
+        v.extend_from_slice(self.as_slice());
+        v.push(0);
+        Self::from_source(v)
+    }
+
+    /// Returns the data as a [`CStr`] if it contains exactly one
+    /// nul byte and that byte is the last one.
+    pub fn as_c_str(&self) -> Option<&CStr> {
+        CStr::from_bytes_with_nul(self.as_slice()).ok()
+    }
+
+    /// Returns `true` if the data equals `other`.
+    ///
+    /// Lengths are compared first, so buffers of different size are
+    /// rejected without touching their contents. Equal-length buffers
+    /// are compared with `memcmp`, which is vectorized on common platforms.
+    #[inline]
+    pub fn content_eq(&self, other: &[u8]) -> bool {
+        self.len() == other.len() && self.as_slice() == other
+    }
+
+    /// Returns the offset of the first occurrence of `needle`.
+    ///
+    /// An empty needle matches at offset `0`.
+    pub fn find(&self, needle: &[u8]) -> Option<usize> {
+        #[cfg(feature = "memchr")]
+        {
+            memchr::memmem::find(self.as_slice(), needle)
+        }
+        #[cfg(not(feature = "memchr"))]
+        {
+            if needle.is_empty() {
+                return Some(0);
+            }
+            self.as_slice()
+                .windows(needle.len())
+                .position(|window| window == needle)
+        }
+    }
+
+    /// Returns the offset of the last occurrence of `needle`.
+    ///
+    /// An empty needle matches at offset `self.len()`.
+    pub fn rfind(&self, needle: &[u8]) -> Option<usize> {
+        #[cfg(feature = "memchr")]
+        {
+            memchr::memmem::rfind(self.as_slice(), needle)
+        }
+        #[cfg(not(feature = "memchr"))]
+        {
+            if needle.is_empty() {
+                return Some(self.len());
+            }
+            self.as_slice()
+                .windows(needle.len())
+                .rposition(|window| window == needle)
+        }
+    }
+
+    /// Returns `true` if `needle` occurs anywhere in the data.
+    pub fn contains(&self, needle: &[u8]) -> bool {
+        self.find(needle).is_some()
+    }
+
+    /// Returns the ranges in which `self` and `other` differ.
+    ///
+    /// Only the common prefix of both is compared, so if the lengths
+    /// differ the caller should treat `min_len..max_len` as differing too.
+    /// Equal regions are skipped a word at a time, which makes this
+    /// cheap for large, mostly identical buffers.
+    pub fn diff_ranges(&self, other: &[u8]) -> Vec<Range<usize>> {
+        const WORD: usize = size_of::<usize>();
+        let len = self.len().min(other.len());
+        let a = &self.as_slice()[..len];
+        let b = &other[..len];
+
+        let mut ranges = Vec::new();
+        let mut start = None;
+        let mut i = 0;
+        while i < len {
+            if start.is_none() && i + WORD <= len && a[i..i + WORD] == b[i..i + WORD] {
+                i += WORD;
+                continue;
+            }
+            if a[i] != b[i] {
+                start.get_or_insert(i);
+            } else if let Some(s) = start.take() {
+                ranges.push(s..i);
+            }
+            i += 1;
+        }
+        if let Some(s) = start {
+            ranges.push(s..len);
+        }
+        ranges
+    }
+
+    /// Returns an iterator over the subslices separated by `delim`.
+    ///
+    /// Like [`slice::split`], a trailing delimiter produces an empty
+    /// final element. All yielded `Bytes` share the owner of `self`.
+    pub fn split(&self, delim: u8) -> Split {
+        Split {
+            rest: self.clone(),
+            delim,
+            finished: false,
+        }
+    }
+
+    /// Returns an iterator over at most `n` subslices separated by `delim`.
+    ///
+    /// The last element contains the remainder of the data.
+    pub fn splitn(&self, n: usize, delim: u8) -> SplitN {
+        SplitN {
+            inner: self.split(delim),
+            count: n,
+        }
+    }
+
+    /// Returns an iterator over the subslices separated by `delim`,
+    /// starting from the end of the data.
+    pub fn rsplit(&self, delim: u8) -> RSplit {
+        RSplit {
+            rest: self.clone(),
+            delim,
+            finished: false,
+        }
+    }
+
+    /// Returns an iterator over the lines of the data.
+    ///
+    /// Lines end with `\n` or `\r\n`, which are not included in the
+    /// yielded `Bytes`. Like [`str::lines`], a final line ending doesn't
+    /// produce an empty trailing line. All yielded `Bytes` share the
+    /// owner of `self`.
+    pub fn lines(&self) -> Lines {
+        Lines { rest: self.clone() }
+    }
+
+    /// Returns an iterator over chunks of `size` bytes, starting at the
+    /// front. The last chunk may be shorter.
+    ///
+    /// All yielded `Bytes` share the owner of `self`.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero.
+    pub fn chunks(&self, size: usize) -> Chunks {
+        assert!(size != 0, "chunk size must be non-zero");
+        Chunks {
+            rest: self.clone(),
+            size,
+        }
+    }
+
+    /// Returns an iterator over chunks of exactly `size` bytes.
+    ///
+    /// Trailing bytes that don't fill a chunk are skipped and can be
+    /// retrieved with [ChunksExact::remainder].
+    ///
+    /// # Panics
+    /// Panics if `size` is zero.
+    pub fn chunks_exact(&self, size: usize) -> ChunksExact {
+        assert!(size != 0, "chunk size must be non-zero");
+        let split = self.len() - self.len() % size;
+        ChunksExact {
+            rest: self.slice(..split),
+            remainder: self.slice(split..),
+            size,
+        }
+    }
+
+    /// Returns an iterator over chunks of `size` bytes, starting at the
+    /// end. The last chunk may be shorter.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero.
+    pub fn rchunks(&self, size: usize) -> RChunks {
+        assert!(size != 0, "chunk size must be non-zero");
+        RChunks {
+            rest: self.clone(),
+            size,
+        }
+    }
+
+    /// Returns the data without leading and trailing ASCII whitespace,
+    /// sharing the owner of `self`.
+    pub fn trim_ascii(&self) -> Self {
+        self.slice_to_bytes(self.as_slice().trim_ascii())
+            .expect("trimmed data is a subslice")
+    }
+
+    /// Returns the data without leading ASCII whitespace.
+    pub fn trim_ascii_start(&self) -> Self {
+        self.slice_to_bytes(self.as_slice().trim_ascii_start())
+            .expect("trimmed data is a subslice")
+    }
+
+    /// Returns the data without trailing ASCII whitespace.
+    pub fn trim_ascii_end(&self) -> Self {
+        self.slice_to_bytes(self.as_slice().trim_ascii_end())
+            .expect("trimmed data is a subslice")
+    }
+}
+
+macro_rules! read_int {
+    ($($le:ident, $be:ident => $t:ty;)*) => {
+        $(
+            #[doc = concat!("Consumes a little-endian `", stringify!($t), "` from the front.")]
+            ///
+            /// Returns `None` and leaves `self` unchanged if too few bytes remain.
+            pub fn $le(&mut self) -> Option<$t> {
+                let prefix = self.take_prefix(size_of::<$t>())?;
+                Some(<$t>::from_le_bytes(prefix.as_slice().try_into().unwrap()))
+            }
+
+            #[doc = concat!("Consumes a big-endian `", stringify!($t), "` from the front.")]
+            ///
+            /// Returns `None` and leaves `self` unchanged if too few bytes remain.
+            pub fn $be(&mut self) -> Option<$t> {
+                let prefix = self.take_prefix(size_of::<$t>())?;
+                Some(<$t>::from_be_bytes(prefix.as_slice().try_into().unwrap()))
+            }
+        )*
+    };
+}
+
+// Fixed-width integer readers.
+impl Bytes {
+    read_int! {
+        read_u16_le, read_u16_be => u16;
+        read_u32_le, read_u32_be => u32;
+        read_u64_le, read_u64_be => u64;
+        read_i16_le, read_i16_be => i16;
+        read_i32_le, read_i32_be => i32;
+        read_i64_le, read_i64_be => i64;
+    }
+}
+
+// Length-prefixed frame readers.
+impl Bytes {
+    /// Consumes a frame prefixed with a big-endian `u32` length
+    /// and returns its payload.
+    ///
+    /// Returns `None` and leaves `self` unchanged if the length or
+    /// the complete payload isn't available yet.
+    pub fn take_frame_be32(&mut self) -> Option<Self> {
+        let data = self.data;
+        let header: [u8; 4] = data.get(..4)?.try_into().unwrap();
+        let len = u32::from_be_bytes(header) as usize;
+        let payload = data[4..].get(..len)?;
+        self.data = &data[4 + len..];
+        Some(Self {
+            data: payload,
+            owner: self.owner.clone(),
+        })
+    }
+}
+
+// LEB128 variable-length integer readers.
+impl Bytes {
+    /// Consumes an unsigned LEB128 encoded integer from the front.
+    ///
+    /// Returns `None` and leaves `self` unchanged if the encoding is
+    /// truncated or doesn't fit into a `u64`.
+    pub fn read_varint_u64(&mut self) -> Option<u64> {
+        let mut value: u64 = 0;
+        for (i, &byte) in self.as_slice().iter().enumerate() {
+            let shift = 7 * i as u32;
+            let bits = (byte & 0x7f) as u64;
+            if shift >= 64 || (shift == 63 && bits > 1) {
+                return None;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                self.data = &self.data[i + 1..];
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// Consumes a signed LEB128 encoded integer from the front.
+    ///
+    /// Returns `None` and leaves `self` unchanged if the encoding is
+    /// truncated or doesn't fit into an `i64`.
+    pub fn read_varint_i64(&mut self) -> Option<i64> {
+        let mut value: i64 = 0;
+        for (i, &byte) in self.as_slice().iter().enumerate() {
+            let shift = 7 * i as u32;
+            let bits = (byte & 0x7f) as i64;
+            if shift >= 64 {
+                return None;
+            }
+            if shift == 63 && bits != 0 && bits != 0x7f {
+                return None;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                if shift < 57 && byte & 0x40 != 0 {
+                    value |= -1 << (shift + 7);
+                }
+                self.data = &self.data[i + 1..];
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+/// Iterator returned by [Bytes::split].
+#[derive(Debug, Clone)]
+pub struct Split {
+    rest: Bytes,
+    delim: u8,
+    finished: bool,
+}
+
+impl Iterator for Split {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        if self.finished {
+            return None;
+        }
+        match self.rest.iter().position(|&b| b == self.delim) {
+            Some(i) => {
+                let head = self.rest.slice(..i);
+                self.rest = self.rest.slice(i + 1..);
+                Some(head)
+            }
+            None => {
+                self.finished = true;
+                Some(std::mem::take(&mut self.rest))
+            }
+        }
+    }
+}
+
+/// Iterator returned by [Bytes::splitn].
+#[derive(Debug, Clone)]
+pub struct SplitN {
+    inner: Split,
+    count: usize,
+}
+
+impl Iterator for SplitN {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                if self.inner.finished {
+                    None
+                } else {
+                    self.inner.finished = true;
+                    Some(std::mem::take(&mut self.inner.rest))
+                }
+            }
+            _ => {
+                self.count -= 1;
+                self.inner.next()
+            }
+        }
+    }
+}
+
+/// Iterator returned by [Bytes::rsplit].
+#[derive(Debug, Clone)]
+pub struct RSplit {
+    rest: Bytes,
+    delim: u8,
+    finished: bool,
+}
+
+impl Iterator for RSplit {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        if self.finished {
+            return None;
+        }
+        match self.rest.iter().rposition(|&b| b == self.delim) {
+            Some(i) => {
+                let tail = self.rest.slice(i + 1..);
+                self.rest = self.rest.slice(..i);
+                Some(tail)
+            }
+            None => {
+                self.finished = true;
+                Some(std::mem::take(&mut self.rest))
+            }
+        }
+    }
+}
+
+/// Iterator returned by [Bytes::lines].
+#[derive(Debug, Clone)]
+pub struct Lines {
+    rest: Bytes,
+}
+
+impl Iterator for Lines {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        match self.rest.iter().position(|&b| b == b'\n') {
+            Some(i) => {
+                let end = if i > 0 && self.rest[i - 1] == b'\r' {
+                    i - 1
+                } else {
+                    i
+                };
+                let line = self.rest.slice(..end);
+                self.rest = self.rest.slice(i + 1..);
+                Some(line)
+            }
+            None => Some(std::mem::take(&mut self.rest)),
+        }
+    }
+}
+
+/// Iterator returned by [Bytes::chunks].
+#[derive(Debug, Clone)]
+pub struct Chunks {
+    rest: Bytes,
+    size: usize,
+}
+
+impl Iterator for Chunks {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let len = self.size.min(self.rest.len());
+        self.rest.take_prefix(len)
+    }
+}
+
+/// Iterator returned by [Bytes::chunks_exact].
+#[derive(Debug, Clone)]
+pub struct ChunksExact {
+    rest: Bytes,
+    remainder: Bytes,
+    size: usize,
+}
+
+impl ChunksExact {
+    /// Returns the trailing bytes that don't fill a whole chunk.
+    pub fn remainder(&self) -> Bytes {
+        self.remainder.clone()
+    }
+}
+
+impl Iterator for ChunksExact {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        self.rest.take_prefix(self.size)
+    }
+}
+
+/// Iterator returned by [Bytes::rchunks].
+#[derive(Debug, Clone)]
+pub struct RChunks {
+    rest: Bytes,
+    size: usize,
+}
+
+impl Iterator for RChunks {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let split = self.rest.len().saturating_sub(self.size);
+        let chunk = self.rest.slice(split..);
+        self.rest = self.rest.slice(..split);
+        Some(chunk)
+    }
 }
 
 impl WeakBytes {
+    /// Creates a [WeakBytes] without an owner, similar to
+    /// [std::sync::Weak::new]. Upgrading it always returns `None`.
+    pub fn new() -> Self {
+        let owner: Weak<dyn ByteOwner> = Weak::<Vec<u8>>::new();
+        WeakBytes {
+            data: &[] as *const [u8],
+            owner,
+        }
+    }
+
     /// The reverse of `downgrade`. Returns `None` if the value was dropped.
     pub fn upgrade(&self) -> Option<Bytes> {
         let arc = self.owner.upgrade()?;
@@ -205,6 +1310,12 @@ impl WeakBytes {
     }
 }
 
+impl Default for WeakBytes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: ByteSource + ByteOwner> From<T> for Bytes {
     fn from(value: T) -> Self {
         Self::from_owning_source(value)
@@ -255,7 +1366,49 @@ impl Default for Bytes {
 
 impl<T: AsRef<[u8]>> PartialEq<T> for Bytes {
     fn eq(&self, other: &T) -> bool {
-        self.as_slice() == other.as_ref()
+        self.content_eq(other.as_ref())
+    }
+}
+
+impl PartialEq<[u8]> for Bytes {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.content_eq(other)
+    }
+}
+
+impl PartialEq<str> for Bytes {
+    fn eq(&self, other: &str) -> bool {
+        self.as_slice() == other.as_bytes()
+    }
+}
+
+impl PartialEq<Bytes> for [u8] {
+    fn eq(&self, other: &Bytes) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl PartialEq<Bytes> for &[u8] {
+    fn eq(&self, other: &Bytes) -> bool {
+        *self == other.as_slice()
+    }
+}
+
+impl PartialEq<Bytes> for Vec<u8> {
+    fn eq(&self, other: &Bytes) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl PartialEq<Bytes> for str {
+    fn eq(&self, other: &Bytes) -> bool {
+        self.as_bytes() == other.as_slice()
+    }
+}
+
+impl PartialEq<Bytes> for &str {
+    fn eq(&self, other: &Bytes) -> bool {
+        str::as_bytes(self) == other.as_slice()
     }
 }
 
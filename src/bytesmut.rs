@@ -0,0 +1,139 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::Bytes;
+
+/// A growable, writable buffer that can be frozen into [Bytes]
+/// without copying.
+///
+/// # Examples
+///
+/// ```
+/// use anybytes::BytesMut;
+/// let mut buf = BytesMut::with_capacity(11);
+/// buf.extend_from_slice(b"hello");
+/// buf.push(b' ');
+/// buf.extend_from_slice(b"world");
+/// buf[0] = b'H';
+/// assert_eq!(buf.freeze(), b"Hello world");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BytesMut {
+    buf: Vec<u8>,
+}
+
+impl BytesMut {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty buffer with room for `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of bytes the buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Reserves room for at least `additional` more bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buf.reserve(additional);
+    }
+
+    /// Appends a single byte.
+    pub fn push(&mut self, byte: u8) {
+        self.buf.push(byte);
+    }
+
+    /// Appends all bytes in `data`.
+    pub fn extend_from_slice(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Shortens the buffer to `len` bytes, keeping its capacity.
+    pub fn truncate(&mut self, len: usize) {
+        self.buf.truncate(len);
+    }
+
+    /// Removes all bytes, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Converts the buffer into immutable [Bytes].
+    ///
+    /// The allocation is moved into the owner of the returned [Bytes]
+    /// and can be recovered with [Bytes::downcast_to_owner] as a `Vec<u8>`.
+    pub fn freeze(self) -> Bytes {
+        Bytes::from_source(self.buf)
+    }
+}
+
+impl Deref for BytesMut {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl DerefMut for BytesMut {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+}
+
+impl From<Vec<u8>> for BytesMut {
+    fn from(buf: Vec<u8>) -> Self {
+        Self { buf }
+    }
+}
+
+impl Extend<u8> for BytesMut {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        self.buf.extend(iter);
+    }
+}
+
+impl<'a> Extend<&'a u8> for BytesMut {
+    fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+        self.buf.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BytesMut;
+
+    #[test]
+    fn freeze_is_zero_copy() {
+        let mut buf = BytesMut::with_capacity(4);
+        buf.extend_from_slice(b"abc");
+        buf.push(b'd');
+        buf.reserve(100);
+        assert!(buf.capacity() >= 104);
+        let ptr = buf.as_ptr();
+
+        let frozen = buf.freeze();
+        assert_eq!(frozen, b"abcd");
+        assert_eq!(frozen.as_ptr(), ptr);
+        let owner = frozen.downcast_to_owner::<Vec<u8>>().unwrap();
+        assert_eq!(owner.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn edit_in_place() {
+        let mut buf = BytesMut::from(b"hello".to_vec());
+        buf[0] = b'j';
+        buf.extend(b" world".iter());
+        buf.truncate(5);
+        assert_eq!(&buf[..], b"jello");
+        buf.clear();
+        assert!(buf.is_empty());
+        assert!(buf.freeze().is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+use crate::Bytes;
+
+/// Memoizes sub-slices of [Bytes], see [Bytes::slice_cached].
+///
+/// Cached entries keep their owner alive until the cache is cleared
+/// or dropped.
+#[derive(Debug, Default)]
+pub struct SliceCache {
+    entries: Mutex<HashMap<(usize, usize, usize), Bytes>>,
+}
+
+impl SliceCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached slices.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no slices are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached slices, releasing their owners.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+impl Bytes {
+    /// Returns a slice of self for `range`, reusing a previously
+    /// cached slice of the same owner and memory range if available.
+    ///
+    /// Slicing is already `O(1)`, so this doesn't make individual slices
+    /// cheaper. It ensures repeated slices of the same region are the
+    /// identical `Bytes` (see [Bytes::ptr_eq]), which lets decoders
+    /// deduplicate shared regions such as schema headers.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds.
+    pub fn slice_cached(&self, range: Range<usize>, cache: &SliceCache) -> Bytes {
+        let data = &self.as_slice()[range];
+        let key = (
+            Arc::as_ptr(&self.owner) as *const u8 as usize,
+            data.as_ptr() as usize,
+            data.len(),
+        );
+        cache
+            .entries
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| self.slice_to_bytes(data).expect("data is a subslice"))
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SliceCache;
+    use crate::Bytes;
+
+    #[test]
+    fn reuses_identical_slices() {
+        let cache = SliceCache::new();
+        let b = Bytes::from(b"header payload".to_vec());
+        let h1 = b.slice_cached(0..6, &cache);
+        let h2 = b.clone().slice_cached(0..6, &cache);
+        assert_eq!(h1, b"header");
+        assert!(h1.ptr_eq(&h2));
+        assert_eq!(cache.len(), 1);
+
+        // The same range of a different owner is cached separately.
+        let other = Bytes::from(b"header payload".to_vec());
+        other.slice_cached(0..6, &cache);
+        assert_eq!(cache.len(), 2);
+
+        // Different ranges that describe the same memory share an entry.
+        let tail = b.slice(7..);
+        let p1 = tail.slice_cached(0..7, &cache);
+        let p2 = b.slice_cached(7..14, &cache);
+        assert!(p1.ptr_eq(&p2));
+        assert_eq!(cache.len(), 3);
+
+        let weak = b.downgrade();
+        drop((b, h1, h2, tail, p1, p2));
+        assert!(weak.upgrade().is_some());
+        cache.clear();
+        assert!(weak.upgrade().is_none());
+    }
+}
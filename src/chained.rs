@@ -0,0 +1,245 @@
+use std::collections::VecDeque;
+use std::io;
+use std::ops::Index;
+
+use crate::Bytes;
+
+/// A sequence of [Bytes] segments that behaves like their concatenation
+/// without copying them.
+///
+/// Reading via [io::Read] (or `bytes::Buf` with the `bytes` feature)
+/// consumes data from the front. Data is only copied when a contiguous
+/// view is requested with [ChainedBytes::to_bytes].
+///
+/// # Examples
+///
+/// ```
+/// use anybytes::{Bytes, ChainedBytes};
+/// let mut chain = ChainedBytes::new();
+/// chain.push(Bytes::from(b"hello ".to_vec()));
+/// chain.push(Bytes::from(b"world".to_vec()));
+/// assert_eq!(chain.len(), 11);
+/// assert_eq!(chain[6], b'w');
+/// assert_eq!(chain.to_bytes(), b"hello world");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ChainedBytes {
+    segments: VecDeque<Bytes>,
+    len: usize,
+}
+
+impl ChainedBytes {
+    /// Creates an empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `bytes` to the end of the chain.
+    pub fn push(&mut self, bytes: Bytes) {
+        if !bytes.is_empty() {
+            self.len += bytes.len();
+            self.segments.push_back(bytes);
+        }
+    }
+
+    /// Returns the total number of bytes in all segments.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the chain holds no data.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the byte at logical `offset`, or `None` if it is out of bounds.
+    ///
+    /// This walks the segments, so it takes time linear in their number.
+    pub fn get(&self, mut offset: usize) -> Option<u8> {
+        for segment in &self.segments {
+            if offset < segment.len() {
+                return Some(segment[offset]);
+            }
+            offset -= segment.len();
+        }
+        None
+    }
+
+    /// Returns an iterator over the non-empty segments in order.
+    pub fn chunks(&self) -> impl Iterator<Item = &Bytes> + '_ {
+        self.segments.iter()
+    }
+
+    /// Returns the data as contiguous [Bytes].
+    ///
+    /// A chain with a single segment returns it without copying,
+    /// otherwise the segments are copied into a new allocation.
+    pub fn to_bytes(&self) -> Bytes {
+        match self.segments.len() {
+            0 => Bytes::empty(),
+            1 => self.segments[0].clone(),
+            _ => {
+                let mut data = Vec::with_capacity(self.len);
+                for segment in &self.segments {
+                    data.extend_from_slice(segment);
+                }
+                Bytes::from_source(data)
+            }
+        }
+    }
+
+    // Consumes `n` bytes from the front.
+    fn advance(&mut self, mut n: usize) {
+        assert!(n <= self.len, "cannot advance past the end of the chain");
+        self.len -= n;
+        while n > 0 {
+            let front = self.segments.front_mut().expect("length covers segments");
+            if n < front.len() {
+                front.take_prefix(n);
+                return;
+            }
+            n -= front.len();
+            self.segments.pop_front();
+        }
+    }
+}
+
+impl Index<usize> for ChainedBytes {
+    type Output = u8;
+
+    fn index(&self, mut offset: usize) -> &u8 {
+        for segment in &self.segments {
+            if offset < segment.len() {
+                return &segment[offset];
+            }
+            offset -= segment.len();
+        }
+        panic!("index out of bounds: the len is {}", self.len);
+    }
+}
+
+impl From<Vec<Bytes>> for ChainedBytes {
+    fn from(segments: Vec<Bytes>) -> Self {
+        segments.into_iter().collect()
+    }
+}
+
+impl FromIterator<Bytes> for ChainedBytes {
+    fn from_iter<I: IntoIterator<Item = Bytes>>(iter: I) -> Self {
+        let mut chain = Self::new();
+        chain.extend(iter);
+        chain
+    }
+}
+
+impl Extend<Bytes> for ChainedBytes {
+    fn extend<I: IntoIterator<Item = Bytes>>(&mut self, iter: I) {
+        for bytes in iter {
+            self.push(bytes);
+        }
+    }
+}
+
+impl io::Read for ChainedBytes {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(front) = self.segments.front() else {
+            return Ok(0);
+        };
+        let n = buf.len().min(front.len());
+        buf[..n].copy_from_slice(&front[..n]);
+        self.advance(n);
+        Ok(n)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl bytes::Buf for ChainedBytes {
+    fn remaining(&self) -> usize {
+        self.len
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.segments.front().map_or(&[], |front| front.as_slice())
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        ChainedBytes::advance(self, cnt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::ChainedBytes;
+    use crate::Bytes;
+
+    fn chain() -> ChainedBytes {
+        vec![
+            Bytes::from(b"ab".to_vec()),
+            Bytes::empty(),
+            Bytes::from(b"cde".to_vec()),
+            Bytes::from(b"f".to_vec()),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn cross_segment_indexing() {
+        let chain = chain();
+        assert_eq!(chain.len(), 6);
+        let all: Vec<u8> = (0..chain.len()).map(|i| chain[i]).collect();
+        assert_eq!(all, b"abcdef");
+        assert_eq!(chain.get(2), Some(b'c'));
+        assert_eq!(chain.get(6), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let _ = chain()[6];
+    }
+
+    #[test]
+    fn chunks_in_order() {
+        let chain = chain();
+        let chunks: Vec<&[u8]> = chain.chunks().map(|c| c.as_slice()).collect();
+        assert_eq!(chunks, [&b"ab"[..], b"cde", b"f"]);
+    }
+
+    #[test]
+    fn flatten() {
+        assert!(ChainedBytes::new().to_bytes().is_empty());
+        assert_eq!(chain().to_bytes(), b"abcdef");
+
+        let single = Bytes::from(b"abc".to_vec());
+        let chain: ChainedBytes = [single.clone(), Bytes::empty()].into_iter().collect();
+        assert!(chain.to_bytes().ptr_eq(&single));
+    }
+
+    #[test]
+    fn read() {
+        let mut chain = chain();
+        let mut buf = [0u8; 4];
+        assert_eq!(chain.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], b"ab");
+        assert_eq!(chain.len(), 4);
+        let mut rest = Vec::new();
+        chain.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"cdef");
+        assert!(chain.is_empty());
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn buf() {
+        use bytes::Buf;
+
+        let mut chain = chain();
+        assert_eq!(chain.chunk(), b"ab");
+        Buf::advance(&mut chain, 3);
+        assert_eq!(chain.chunk(), b"de");
+        assert_eq!(chain.copy_to_bytes(3).as_ref(), b"def");
+        assert_eq!(chain.remaining(), 0);
+    }
+}
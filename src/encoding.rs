@@ -0,0 +1,267 @@
+//! Hex and base64 text encodings and hex dumps for [Bytes].
+
+use std::fmt;
+
+use crate::Bytes;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Error returned by [Bytes::from_hex].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexError {
+    /// The input has an odd number of digits.
+    OddLength,
+    /// The input contains a non-hex character at `index`.
+    InvalidChar { index: usize },
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexError::OddLength => f.write_str("hex input has an odd number of digits"),
+            HexError::InvalidChar { index } => {
+                write!(f, "invalid hex character at index {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Error returned by [Bytes::from_base64].
+#[cfg(feature = "base64")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Error {
+    /// The input length is not a multiple of four.
+    InvalidLength,
+    /// The input contains a character outside the standard alphabet,
+    /// or misplaced padding, at `index`.
+    InvalidChar { index: usize },
+}
+
+#[cfg(feature = "base64")]
+impl fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base64Error::InvalidLength => {
+                f.write_str("base64 input length is not a multiple of four")
+            }
+            Base64Error::InvalidChar { index } => {
+                write!(f, "invalid base64 character at index {index}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "base64")]
+impl std::error::Error for Base64Error {}
+
+#[cfg(feature = "base64")]
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[cfg(feature = "base64")]
+fn base64_value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Formats [Bytes] as a canonical hex dump, see [Bytes::hexdump].
+#[derive(Debug, Clone, Copy)]
+pub struct HexDump<'a> {
+    data: &'a [u8],
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (line, chunk) in self.data.chunks(16).enumerate() {
+            write!(f, "{:08x}:", line * 16)?;
+            for i in 0..16 {
+                if i % 2 == 0 {
+                    f.write_str(" ")?;
+                }
+                match chunk.get(i) {
+                    Some(b) => write!(f, "{b:02x}")?,
+                    None => f.write_str("  ")?,
+                }
+            }
+            f.write_str("  ")?;
+            for &b in chunk {
+                let c = if (0x20..0x7f).contains(&b) {
+                    b as char
+                } else {
+                    '.'
+                };
+                write!(f, "{c}")?;
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl Bytes {
+    /// Returns a [Display](fmt::Display) adapter that formats the data
+    /// like `xxd`: an offset, 16 bytes as hex and an ASCII column per line.
+    ///
+    /// The output is written line by line, so dumping large buffers
+    /// doesn't build an intermediate `String`.
+    pub fn hexdump(&self) -> HexDump<'_> {
+        HexDump {
+            data: self.as_slice(),
+        }
+    }
+
+    /// Encodes the data as lowercase hex.
+    pub fn to_hex(&self) -> String {
+        let mut out = String::with_capacity(self.len() * 2);
+        for &b in self.iter() {
+            out.push(HEX_DIGITS[(b >> 4) as usize] as char);
+            out.push(HEX_DIGITS[(b & 0xf) as usize] as char);
+        }
+        out
+    }
+
+    /// Decodes hex digits of either case into new `Bytes`.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn from_hex(s: &str) -> Result<Bytes, HexError> {
+        let s = s.as_bytes();
+        if s.len() % 2 != 0 {
+            return Err(HexError::OddLength);
+        }
+        let mut out = Vec::with_capacity(s.len() / 2);
+        for (i, pair) in s.chunks_exact(2).enumerate() {
+            let hi = hex_value(pair[0]).ok_or(HexError::InvalidChar { index: 2 * i })?;
+            let lo = hex_value(pair[1]).ok_or(HexError::InvalidChar { index: 2 * i + 1 })?;
+            out.push(hi << 4 | lo);
+        }
+        Ok(Bytes::from_source(out))
+    }
+
+    /// Encodes the data as padded base64 using the standard alphabet.
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        let mut out = String::with_capacity(self.len().div_ceil(3) * 4);
+        for chunk in self.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    let index = (n >> (18 - 6 * i)) & 0x3f;
+                    out.push(BASE64_ALPHABET[index as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    /// Decodes padded base64 in the standard alphabet into new `Bytes`.
+    #[cfg(feature = "base64")]
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn from_base64(s: &str) -> Result<Bytes, Base64Error> {
+        let s = s.as_bytes();
+        if s.len() % 4 != 0 {
+            return Err(Base64Error::InvalidLength);
+        }
+        let mut out = Vec::with_capacity(s.len() / 4 * 3);
+        for (i, quad) in s.chunks_exact(4).enumerate() {
+            let last = i + 1 == s.len() / 4;
+            let padding = match quad {
+                [_, _, b'=', b'='] if last => 2,
+                [_, _, _, b'='] if last => 1,
+                _ => 0,
+            };
+            let mut n = 0u32;
+            for (j, &c) in quad[..4 - padding].iter().enumerate() {
+                let value = base64_value(c).ok_or(Base64Error::InvalidChar { index: 4 * i + j })?;
+                n |= (value as u32) << (18 - 6 * j);
+            }
+            out.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+        }
+        Ok(Bytes::from_source(out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HexError;
+    use crate::Bytes;
+
+    #[test]
+    fn hex_roundtrip() {
+        let b = Bytes::from(vec![0x00u8, 0x7f, 0xab, 0xff]);
+        assert_eq!(b.to_hex(), "007fabff");
+        assert_eq!(Bytes::from_hex("007FabfF").unwrap(), b);
+        assert!(Bytes::from_hex("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn hex_malformed() {
+        assert_eq!(Bytes::from_hex("abc"), Err(HexError::OddLength));
+        assert_eq!(
+            Bytes::from_hex("0g"),
+            Err(HexError::InvalidChar { index: 1 })
+        );
+        assert_eq!(
+            Bytes::from_hex("00 1").unwrap_err().to_string(),
+            "invalid hex character at index 2"
+        );
+    }
+
+    #[test]
+    fn hexdump() {
+        let b = Bytes::from(b"Hello, world!\n\x00\x01\xffabcdefgh".to_vec());
+        let expected = "\
+00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a 0001  Hello, world!...
+00000010: ff61 6263 6465 6667 68                   .abcdefgh
+";
+        assert_eq!(b.hexdump().to_string(), expected);
+        assert_eq!(Bytes::empty().hexdump().to_string(), "");
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn base64_known_values() {
+        use super::Base64Error;
+
+        for (plain, encoded) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foobar", "Zm9vYmFy"),
+        ] {
+            let b = Bytes::from(plain.to_vec());
+            assert_eq!(b.to_base64(), encoded);
+            assert_eq!(Bytes::from_base64(encoded).unwrap(), plain);
+        }
+        assert_eq!(Bytes::from_base64("Zm9"), Err(Base64Error::InvalidLength));
+        assert_eq!(
+            Bytes::from_base64("Zm=v"),
+            Err(Base64Error::InvalidChar { index: 2 })
+        );
+        assert_eq!(
+            Bytes::from_base64("Zg==Zg=="),
+            Err(Base64Error::InvalidChar { index: 2 })
+        );
+    }
+}
@@ -9,7 +9,15 @@
 #![doc = include_str!("../README.md")]
 
 pub mod bytes;
+mod bytesmut;
+mod cache;
+mod chained;
+mod encoding;
+mod log;
+mod merge;
 mod owners;
+mod textbuilder;
+mod writer;
 
 #[cfg(feature = "zerocopy")]
 pub mod packed;
@@ -17,12 +25,42 @@ pub mod packed;
 #[cfg(feature = "pyo3")]
 pub mod pybytes;
 
+#[cfg(feature = "proptest-support")]
+pub mod proptest;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "blake3")]
+mod store;
+
 #[cfg(test)]
 mod tests;
 
 pub use crate::bytes::ByteSource;
+#[cfg(all(feature = "mmap", unix))]
+pub use crate::bytes::Advice;
+#[cfg(feature = "derive")]
+pub use anybytes_derive::ByteSource;
+pub use crate::bytes::CopyError;
+pub use crate::bytes::TextOwner;
 pub use crate::bytes::Bytes;
+pub use crate::bytesmut::BytesMut;
 pub use crate::bytes::WeakBytes;
+pub use crate::cache::SliceCache;
+pub use crate::chained::ChainedBytes;
+pub use crate::encoding::HexDump;
+pub use crate::encoding::HexError;
+#[cfg(feature = "base64")]
+pub use crate::encoding::Base64Error;
+pub use crate::log::LogReader;
+pub use crate::merge::merge_sorted_records;
+pub use crate::merge::MergeSortedRecords;
+pub use crate::textbuilder::BytesTextBuilder;
+pub use crate::writer::BytesWriter;
+pub use crate::writer::write_all_vectored;
+#[cfg(feature = "mmap-accounting")]
+pub use crate::owners::mapped_bytes_total;
 #[cfg(feature = "zerocopy")]
 pub use crate::packed::Packed;
 #[cfg(feature = "zerocopy")]
@@ -31,3 +69,5 @@ pub use crate::packed::PackedSlice;
 pub use crate::packed::PackedStr;
 #[cfg(feature = "pyo3")]
 pub use crate::pybytes::PyBytes;
+#[cfg(feature = "blake3")]
+pub use crate::store::ContentStore;
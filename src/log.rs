@@ -0,0 +1,94 @@
+use crate::Bytes;
+
+/// Iterates the records of an append-only log.
+///
+/// Each record is a big-endian `u32` length followed by that many bytes
+/// of payload, see [Bytes::take_frame_be32]. Payloads are yielded as
+/// zero-copy [Bytes]. A trailing record that was only partially written
+/// ends the iteration and stays available via [LogReader::remaining].
+///
+/// # Examples
+///
+/// ```
+/// use anybytes::{Bytes, LogReader};
+/// let log = Bytes::from(b"\0\0\0\x02ab\0\0\0\x05c".to_vec());
+/// let mut reader = LogReader::new(log);
+/// assert_eq!(reader.next().unwrap(), b"ab");
+/// assert!(reader.next().is_none());
+/// assert_eq!(reader.remaining(), b"\0\0\0\x05c");
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogReader {
+    rest: Bytes,
+}
+
+impl LogReader {
+    /// Creates a reader over the records in `log`.
+    pub fn new(log: Bytes) -> Self {
+        Self { rest: log }
+    }
+
+    /// Returns the data that hasn't been consumed as complete records.
+    pub fn remaining(&self) -> &Bytes {
+        &self.rest
+    }
+
+    /// Returns the byte offset of the first unconsumed record
+    /// relative to `log`, the `Bytes` the reader was created from.
+    pub fn offset_in(&self, log: &Bytes) -> Option<usize> {
+        let start = log.as_ptr() as usize;
+        let current = self.rest.as_ptr() as usize;
+        current
+            .checked_sub(start)
+            .filter(|&offset| offset <= log.len())
+    }
+}
+
+impl Iterator for LogReader {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        self.rest.take_frame_be32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogReader;
+    use crate::Bytes;
+
+    fn encode(records: &[&[u8]]) -> Vec<u8> {
+        let mut log = Vec::new();
+        for record in records {
+            log.extend_from_slice(&(record.len() as u32).to_be_bytes());
+            log.extend_from_slice(record);
+        }
+        log
+    }
+
+    #[test]
+    fn reads_all_records() {
+        let log = Bytes::from(encode(&[b"first", b"", b"third"]));
+        let records: Vec<Bytes> = LogReader::new(log.clone()).collect();
+        assert_eq!(records, [&b"first"[..], b"", b"third"]);
+        assert!(records.iter().all(|r| r.owner_ptr_eq(&log)));
+    }
+
+    #[test]
+    fn stops_at_torn_tail() {
+        let mut data = encode(&[b"first", b"second"]);
+        let complete = data.len();
+        data.extend_from_slice(&10u32.to_be_bytes());
+        data.extend_from_slice(b"part");
+        let log = Bytes::from(data);
+
+        let mut reader = LogReader::new(log.clone());
+        assert_eq!(reader.by_ref().count(), 2);
+        assert_eq!(reader.offset_in(&log), Some(complete));
+        assert_eq!(reader.remaining().len(), 8);
+
+        let mut reader = LogReader::new(log.slice(..complete + 2));
+        assert_eq!(reader.by_ref().count(), 2);
+        assert_eq!(reader.remaining().len(), 2);
+    }
+}
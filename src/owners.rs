@@ -9,7 +9,10 @@
 #[cfg(feature = "zerocopy")]
 use zerocopy::AsBytes;
 
-use crate::{bytes::ByteOwner, ByteSource};
+use std::sync::Arc;
+
+use crate::bytes::{erase_lifetime, ByteOwner, TextOwner};
+use crate::{ByteSource, Bytes};
 
 #[cfg(feature = "zerocopy")]
 unsafe impl<T> ByteSource for &'static [T]
@@ -96,7 +99,56 @@ unsafe impl ByteSource for Vec<u8> {
     }
         
     fn as_owner(self) -> Self::Owner {
-        todo!()
+        self
+    }
+}
+
+// Arrays store their data inline, so moving them moves the data.
+// They therefore can't be a `ByteSource` and are only supported as
+// owners that are placed into their final `Arc` before being borrowed.
+#[cfg(feature = "zerocopy")]
+impl<T, const N: usize> ByteOwner for [T; N]
+where
+    T: AsBytes + Sync + Send + 'static,
+{
+    fn as_any(self: Arc<Self>) -> Arc<dyn std::any::Any + Sync + Send> {
+        self
+    }
+
+    fn owner_bytes(&self) -> Option<&[u8]> {
+        Some(AsBytes::as_bytes(self))
+    }
+}
+
+#[cfg(feature = "zerocopy")]
+impl<T, const N: usize> From<[T; N]> for Bytes
+where
+    T: AsBytes + Sync + Send + 'static,
+{
+    fn from(array: [T; N]) -> Self {
+        let owner = Arc::new(array);
+        let data = unsafe { erase_lifetime(AsBytes::as_bytes(&*owner)) };
+        Bytes { data, owner }
+    }
+}
+
+#[cfg(not(feature = "zerocopy"))]
+impl<const N: usize> ByteOwner for [u8; N] {
+    fn as_any(self: Arc<Self>) -> Arc<dyn std::any::Any + Sync + Send> {
+        self
+    }
+
+    fn owner_bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
+
+#[cfg(not(feature = "zerocopy"))]
+impl<const N: usize> From<[u8; N]> for Bytes {
+    fn from(array: [u8; N]) -> Self {
+        let owner = Arc::new(array);
+        let data = unsafe { erase_lifetime(&owner[..]) };
+        Bytes { data, owner }
     }
 }
 
@@ -112,6 +164,39 @@ unsafe impl ByteSource for String {
     }
 }
 
+unsafe impl TextOwner for String {}
+
+#[cfg(not(feature = "zerocopy"))]
+unsafe impl ByteSource for Box<str> {
+    type Owner = Self;
+
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    fn as_owner(self) -> Self::Owner {
+        self
+    }
+}
+
+unsafe impl TextOwner for Box<str> {}
+
+unsafe impl ByteSource for Arc<str> {
+    type Owner = Self;
+
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    fn as_owner(self) -> Self::Owner {
+        self
+    }
+}
+
+unsafe impl TextOwner for Arc<str> {}
+
+unsafe impl TextOwner for &'static str {}
+
 unsafe impl ByteSource for &'static str {
     type Owner = Self;
 
@@ -137,6 +222,19 @@ unsafe impl ByteSource for bytes::Bytes {
     }
 }
 
+#[cfg(feature = "bytes")]
+unsafe impl ByteSource for bytes::BytesMut {
+    type Owner = Self;
+
+    fn as_bytes(&self) -> &[u8] {
+        self.as_ref()
+    }
+
+    fn as_owner(self) -> Self::Owner {
+        self
+    }
+}
+
 #[cfg(feature = "ownedbytes")]
 unsafe impl ByteSource for ownedbytes::OwnedBytes {
     type Owner = Self;
@@ -163,6 +261,63 @@ unsafe impl ByteSource for memmap2::Mmap {
     }
 }
 
+#[cfg(feature = "mmap-accounting")]
+static MAPPED_BYTES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Returns the total number of bytes currently mapped by live
+/// [Bytes](crate::Bytes) created through `Bytes::map_*`.
+///
+/// A map taken out with `Bytes::downcast_to_owner` is no longer
+/// counted once the last [Bytes](crate::Bytes) referencing it is dropped.
+#[cfg(feature = "mmap-accounting")]
+pub fn mapped_bytes_total() -> usize {
+    MAPPED_BYTES.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// A memory map created by `Bytes::map_*`, optionally keeping
+/// the mapped file open.
+///
+/// The map is shared so that `Bytes::downcast_to_owner` can hand
+/// it out as a plain `memmap2::Mmap`.
+#[cfg(feature = "mmap")]
+pub(crate) struct MappedFile {
+    pub(crate) mmap: std::sync::Arc<memmap2::Mmap>,
+    _file: Option<std::fs::File>,
+}
+
+#[cfg(feature = "mmap")]
+impl MappedFile {
+    pub(crate) fn new(mmap: memmap2::Mmap, file: Option<std::fs::File>) -> Self {
+        #[cfg(feature = "mmap-accounting")]
+        MAPPED_BYTES.fetch_add(mmap.len(), std::sync::atomic::Ordering::Relaxed);
+        Self {
+            mmap: std::sync::Arc::new(mmap),
+            _file: file,
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl Drop for MappedFile {
+    fn drop(&mut self) {
+        #[cfg(feature = "mmap-accounting")]
+        MAPPED_BYTES.fetch_sub(self.mmap.len(), std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "mmap")]
+unsafe impl ByteSource for MappedFile {
+    type Owner = Self;
+
+    fn as_bytes(&self) -> &[u8] {
+        self.mmap.as_ref()
+    }
+
+    fn as_owner(self) -> Self::Owner {
+        self
+    }
+}
+
 #[cfg(feature = "pyo3")]
 impl ByteOwner for pyo3::Py<pyo3::types::PyBytes> {
     fn as_any(self: std::sync::Arc<Self>) -> std::sync::Arc<dyn std::any::Any + Sync + Send> {
@@ -2,44 +2,190 @@ mod packedscalar;
 mod packedslice;
 mod packedstr;
 
+use std::fmt;
 use std::mem::replace;
+use std::str::Utf8Error;
 
 pub use packedscalar::Packed;
 pub use packedslice::PackedSlice;
 pub use packedstr::PackedStr;
-use zerocopy::FromBytes;
+use zerocopy::{AsBytes, FromBytes};
 
 use crate::Bytes;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum PackError {
+    /// The data doesn't have the size required by the type.
     BadLayout,
+    /// The data has the right size but isn't aligned for the type.
+    Alignment,
 }
 
+impl PackError {
+    // Classifies why `bytes` couldn't be interpreted as a `T`/`[T]`.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub(crate) fn for_data<T>(bytes: &[u8], size_ok: bool) -> Self {
+        if size_ok && (bytes.as_ptr() as usize) % align_of::<T>() != 0 {
+            PackError::Alignment
+        } else {
+            PackError::BadLayout
+        }
+    }
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackError::BadLayout => {
+                f.write_str("bytes have incompatible layout for the target type")
+            }
+            PackError::Alignment => f.write_str("bytes are not aligned for the target type"),
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
 impl Bytes {
+    /// Converts the Bytes into a [PackedStr] if they are valid UTF-8.
+    pub fn try_into_packed_str(self) -> Result<PackedStr, Utf8Error> {
+        self.try_into()
+    }
+
     pub fn packed_prefix<T>(&mut self) -> Option<Packed<T>>
     where
         T: FromBytes,
     {
-        let slice = self.as_slice();
         let size = size_of::<T>();
-        let (data, rest) = slice.split_at(size);
-        let data = self.slice_to_bytes(data)?;
-        let rest = self.slice_to_bytes(rest)?;
+        if self.len() < size {
+            return None;
+        }
+        let data = self.slice(..size);
+        let rest = self.slice(size..);
         let packed: Packed<T> = data.try_into().ok()?;
         _ = replace(self, rest);
         Some(packed)
     }
 
+    /// Interprets the data as a [Packed] `T`, copying it into an aligned
+    /// allocation if it is misaligned and at most `max_copy` bytes long.
+    ///
+    /// Larger misaligned data yields [PackError::Alignment] instead, so
+    /// a misaligned multi-gigabyte mapping is never copied by accident.
+    pub fn packed_or_copy<T>(self, max_copy: usize) -> Result<Packed<T>, PackError>
+    where
+        T: FromBytes + AsBytes + Sync + Send + 'static,
+    {
+        match Packed::try_from(&self) {
+            Err(PackError::Alignment) if self.len() <= max_copy => {
+                let value = T::read_from(self.as_slice()).ok_or(PackError::BadLayout)?;
+                Ok(Box::new(value).into())
+            }
+            result => result,
+        }
+    }
+
+    /// Memory maps `file` and interprets its contents as `P`,
+    /// e.g. a [Packed] or [PackedSlice].
+    ///
+    /// Mappings are page aligned, so the alignment requirements of
+    /// typical on-disk structures are met.
+    ///
+    /// # Safety
+    /// See [Bytes::map_file].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map_file_as<P>(file: &std::fs::File) -> std::io::Result<Result<P, PackError>>
+    where
+        P: TryFrom<Bytes, Error = PackError>,
+    {
+        let bytes = Self::map_file(file)?;
+        Ok(bytes.try_into())
+    }
+
+    /// Reads a `T` from the front of the data by copying it out,
+    /// without consuming any bytes.
+    ///
+    /// Unlike [Packed], which hands out references into the data and
+    /// therefore requires it to be aligned for `T`, this works at any
+    /// alignment. It is the way to read `#[repr(packed)]` types, whose
+    /// fields can never be safely referenced in place. Returns `None`
+    /// if there are fewer than `size_of::<T>()` bytes.
+    pub fn copy_to_packed<T>(&self) -> Option<T>
+    where
+        T: FromBytes + Copy,
+    {
+        T::read_from_prefix(self.as_slice())
+    }
+
+    /// Consumes `N` elements of `T` from the front as a fixed-size array.
+    ///
+    /// Returns `None` and leaves `self` unchanged if there aren't enough
+    /// bytes or they aren't suitably aligned for `T`.
+    pub fn packed_array_prefix<T, const N: usize>(&mut self) -> Option<Packed<[T; N]>>
+    where
+        T: FromBytes,
+    {
+        self.packed_prefix::<[T; N]>()
+    }
+
+    /// Splits off a `T` header from the front and returns it together
+    /// with the remaining payload.
+    pub fn split_header<T>(self) -> Result<(Packed<T>, Bytes), PackError>
+    where
+        T: FromBytes,
+    {
+        let size = size_of::<T>();
+        if self.len() < size {
+            return Err(PackError::BadLayout);
+        }
+        let header = self.slice(..size);
+        let payload = self.slice(size..);
+        Ok((header.try_into()?, payload))
+    }
+
+    /// Splits the data into an unaligned head, the longest aligned
+    /// middle that forms a [PackedSlice] of `T` and the remaining tail,
+    /// like [`slice::align_to`] but without copying.
+    ///
+    /// # Panics
+    /// Panics if `T` is zero-sized.
+    pub fn align_to<T>(&self) -> (Bytes, PackedSlice<T>, Bytes)
+    where
+        T: FromBytes,
+    {
+        let size = size_of::<T>();
+        assert!(size != 0, "align_to requires a sized element type");
+        let len = self.len();
+        let offset = self.as_ptr().align_offset(align_of::<T>());
+        if offset > len {
+            // No aligned position lies within the data.
+            let empty: &'static [u8] = unsafe {
+                std::slice::from_raw_parts(std::ptr::NonNull::<T>::dangling().as_ptr().cast(), 0)
+            };
+            let body = Bytes::from_source(empty)
+                .try_into()
+                .expect("empty and aligned");
+            return (self.clone(), body, Bytes::empty());
+        }
+        let end = offset + (len - offset) / size * size;
+        let body = self
+            .slice(offset..end)
+            .try_into()
+            .expect("body is aligned and sized for T");
+        (self.slice(..offset), body, self.slice(end..))
+    }
+
     pub fn packedslice_prefix<T>(&mut self, count: usize) -> Option<PackedSlice<T>>
     where
         T: FromBytes,
     {
-        let slice = self.as_slice();
-        let size = size_of::<T>() * count;
-        let (data, rest) = slice.split_at(size);
-        let data = self.slice_to_bytes(data)?;
-        let rest = self.slice_to_bytes(rest)?;
+        let size = size_of::<T>().checked_mul(count)?;
+        if self.len() < size {
+            return None;
+        }
+        let data = self.slice(..size);
+        let rest = self.slice(size..);
         let packedslice: PackedSlice<T> = data.try_into().ok()?;
         _ = replace(self, rest);
         Some(packedslice)
@@ -49,11 +195,12 @@ impl Bytes {
     where
         T: FromBytes,
     {
-        let slice = self.as_slice();
-        let size = size_of::<T>() * count;
-        let (data, rest) = slice.split_at(size);
-        let data = self.slice_to_bytes(data)?;
-        let rest = self.slice_to_bytes(rest)?;
+        let size = size_of::<T>().checked_mul(count)?;
+        if self.len() < size {
+            return None;
+        }
+        let data = self.slice(..size);
+        let rest = self.slice(size..);
         let packedstr: PackedStr = data.try_into().ok()?;
         _ = replace(self, rest);
         Some(packedstr)
@@ -63,11 +210,10 @@ impl Bytes {
     where
         T: FromBytes,
     {
-        let slice = self.as_slice();
         let size = size_of::<T>();
-        let (data, rest) = slice.split_at(slice.len() - size);
-        let data = self.slice_to_bytes(data)?;
-        let rest = self.slice_to_bytes(rest)?;
+        let split = self.len().checked_sub(size)?;
+        let rest = self.slice(..split);
+        let data = self.slice(split..);
         let packed: Packed<T> = data.try_into().ok()?;
         _ = replace(self, rest);
         Some(packed)
@@ -77,21 +223,19 @@ impl Bytes {
     where
         T: FromBytes,
     {
-        let slice = self.as_slice();
-        let size = size_of::<T>() * count;
-        let (data, rest) = slice.split_at(slice.len() - size);
-        let data = self.slice_to_bytes(data)?;
-        let rest = self.slice_to_bytes(rest)?;
+        let size = size_of::<T>().checked_mul(count)?;
+        let split = self.len().checked_sub(size)?;
+        let rest = self.slice(..split);
+        let data = self.slice(split..);
         let packedslice: PackedSlice<T> = data.try_into().ok()?;
         _ = replace(self, rest);
         Some(packedslice)
     }
 
     pub fn packedstr_suffix(&mut self, size: usize) -> Option<PackedStr> {
-        let slice = self.as_slice();
-        let (data, rest) = slice.split_at(slice.len() - size);
-        let data = self.slice_to_bytes(data)?;
-        let rest = self.slice_to_bytes(rest)?;
+        let split = self.len().checked_sub(size)?;
+        let rest = self.slice(..split);
+        let data = self.slice(split..);
         let packedstr: PackedStr = data.try_into().ok()?;
         _ = replace(self, rest);
         Some(packedstr)
@@ -4,6 +4,19 @@ use zerocopy::{AsBytes, FromBytes};
 
 use super::PackError;
 
+/// A typed handle to a `T` stored in [Bytes].
+///
+/// `Packed<T>` already behaves like an `Arc<T>`: cloning it is cheap,
+/// it dereferences to `T` and it keeps the underlying owner alive.
+///
+/// Construction requires `T: FromBytes`, so every bit pattern is a valid
+/// `T` and no further validation is needed. Types with invalid bit
+/// patterns, like `bool`, are rejected at compile time:
+///
+/// ```compile_fail
+/// use anybytes::{Bytes, Packed};
+/// let p: Packed<bool> = Bytes::from(vec![2u8]).try_into().unwrap();
+/// ```
 pub struct Packed<T> {
     bytes: Bytes,
     _type: PhantomData<T>,
@@ -28,6 +41,49 @@ impl<T> Packed<T> {
     pub fn bytes(&self) -> Bytes {
         self.bytes.clone()
     }
+
+    /// Reinterprets the underlying bytes as a `U`, keeping the owner.
+    ///
+    /// The bytes are validated for `U` again, so this fails like
+    /// [TryFrom] if their size or alignment doesn't fit.
+    pub fn cast<U>(self) -> Result<Packed<U>, PackError>
+    where
+        U: FromBytes,
+    {
+        self.bytes.try_into()
+    }
+
+    /// Projects to a part of the value, e.g. a struct field, returning
+    /// it as a [Packed] that shares the owner.
+    ///
+    /// Returns `None` if the reference returned by `f` doesn't point
+    /// into the bytes of `self`.
+    pub fn map<U, F>(&self, f: F) -> Option<Packed<U>>
+    where
+        T: FromBytes,
+        U: FromBytes + AsBytes,
+        F: FnOnce(&T) -> &U,
+    {
+        let field = f(self.deref());
+        self.bytes.slice_to_bytes(field.as_bytes())?.try_into().ok()
+    }
+
+    /// Returns another handle sharing the same owner.
+    ///
+    /// This is an alias for `clone` for code that wants to make the
+    /// shared ownership explicit.
+    pub fn shared(&self) -> Self {
+        self.clone()
+    }
+
+    /// Copies the value into a standalone `Arc<T>` that no longer
+    /// references the underlying bytes.
+    pub fn to_arc(&self) -> Arc<T>
+    where
+        T: FromBytes + Clone,
+    {
+        Arc::new(self.deref().clone())
+    }
 }
 
 impl<T> Clone for Packed<T> {
@@ -93,7 +149,10 @@ where
 
     fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
         if <T as FromBytes>::ref_from(&bytes).is_none() {
-            Err(PackError::BadLayout)
+            Err(PackError::for_data::<T>(
+                &bytes,
+                bytes.len() == size_of::<T>(),
+            ))
         } else {
             Ok(Packed {
                 bytes,
@@ -111,7 +170,10 @@ where
 
     fn try_from(bytes: &Bytes) -> Result<Self, Self::Error> {
         if <T as FromBytes>::ref_from(bytes).is_none() {
-            Err(PackError::BadLayout)
+            Err(PackError::for_data::<T>(
+                bytes,
+                bytes.len() == size_of::<T>(),
+            ))
         } else {
             Ok(Packed {
                 bytes: bytes.clone(),
@@ -153,6 +215,33 @@ where
 
 impl<T> Eq for Packed<T> where T: FromBytes + std::cmp::Eq {}
 
+impl<T> PartialEq<T> for Packed<T>
+where
+    T: FromBytes + std::cmp::PartialEq,
+{
+    fn eq(&self, other: &T) -> bool {
+        self.deref() == other
+    }
+}
+
+impl<T> PartialOrd for Packed<T>
+where
+    T: FromBytes + std::cmp::PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.deref().partial_cmp(other.deref())
+    }
+}
+
+impl<T> PartialOrd<T> for Packed<T>
+where
+    T: FromBytes + std::cmp::PartialOrd,
+{
+    fn partial_cmp(&self, other: &T) -> Option<std::cmp::Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+
 impl<T> Hash for Packed<T>
 where
     T: FromBytes + Hash,
@@ -165,7 +254,8 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::Packed;
+    use crate::packed::PackError;
+    use crate::{Bytes, Packed};
 
     #[test]
     fn roundtrip_copy() {
@@ -183,6 +273,165 @@ mod test {
         let r = *p;
         assert_eq!(l, r)
     }
+
+    #[test]
+    fn compare_with_value() {
+        let p = Packed::copy_from(&42usize);
+        assert!(p == 42);
+        assert!(p < 43);
+        assert_eq!(p.partial_cmp(&41), Some(std::cmp::Ordering::Greater));
+        assert!(p < Packed::copy_from(&43usize));
+    }
+
+    #[test]
+    fn shared_and_to_arc() {
+        let p = Packed::copy_from(&42usize);
+        let s = p.shared();
+        assert!(p.bytes().ptr_eq(&s.bytes()));
+        let a = p.to_arc();
+        drop(p);
+        drop(s);
+        assert_eq!(*a, 42);
+    }
+
+    #[derive(zerocopy::FromZeroes, zerocopy::FromBytes, Clone, Copy)]
+    #[repr(C, packed)]
+    struct PackedHeader {
+        tag: u8,
+        value: u32,
+    }
+
+    #[test]
+    fn copy_to_packed() {
+        let b = Bytes::from(vec![0u8, 7, 1, 0, 0, 0, 9]);
+        let unaligned = b.slice(1..);
+        let header: PackedHeader = unaligned.copy_to_packed().unwrap();
+        assert_eq!({ header.tag }, 7);
+        assert_eq!({ header.value }, u32::from_ne_bytes([1, 0, 0, 0]));
+        assert!(unaligned
+            .slice(..4)
+            .copy_to_packed::<PackedHeader>()
+            .is_none());
+        assert_eq!(
+            b.slice(3..).copy_to_packed::<u32>(),
+            Some(u32::from_ne_bytes([0, 0, 0, 9]))
+        );
+    }
+
+    #[test]
+    fn array_prefix() {
+        let mut b = Bytes::from(vec![1u16, 2, 3, 4]);
+        let head = b.packed_array_prefix::<u16, 3>().unwrap();
+        assert_eq!(*head, [1, 2, 3]);
+        assert_eq!(b.len(), 2);
+        assert!(b.packed_array_prefix::<u16, 3>().is_none());
+        assert_eq!(b.len(), 2);
+    }
+
+    #[test]
+    fn prefix_suffix_short_input() {
+        let mut b = Bytes::from(vec![1u32, 2]).slice(..7);
+        assert!(b.packed_prefix::<u64>().is_none());
+        assert!(b.packedslice_prefix::<u32>(2).is_none());
+        assert!(b.packed_suffix::<u64>().is_none());
+        assert!(b.packedslice_suffix::<u32>(2).is_none());
+        assert_eq!(b.len(), 7);
+
+        let mut b = Bytes::from(vec![1u32, 2, 3]);
+        assert_eq!(*b.packed_suffix::<u32>().unwrap(), 3);
+        assert_eq!(&b.packedslice_suffix::<u32>(1).unwrap()[..], &[2]);
+        assert_eq!(*b.packed_prefix::<u32>().unwrap(), 1);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn packed_or_copy() {
+        let b = Bytes::from(vec![0u32, 7, 0]);
+        let misaligned = b.slice(2..6);
+        let expected = u32::from_ne_bytes(misaligned[..].try_into().unwrap());
+        assert_eq!(
+            Packed::<u32>::try_from(&misaligned).err(),
+            Some(PackError::Alignment)
+        );
+        assert_eq!(
+            misaligned.clone().packed_or_copy::<u32>(3).err(),
+            Some(PackError::Alignment)
+        );
+        let p = misaligned.packed_or_copy::<u32>(4).unwrap();
+        assert_eq!(*p, expected);
+
+        let aligned = b.slice(4..8);
+        let p = aligned.clone().packed_or_copy::<u32>(0).unwrap();
+        assert!(p.bytes().ptr_eq(&aligned));
+
+        assert_eq!(
+            b.slice(0..3).packed_or_copy::<u32>(100).err(),
+            Some(PackError::BadLayout)
+        );
+    }
+
+    #[test]
+    fn error_display() {
+        fn header(b: Bytes) -> Result<u32, Box<dyn std::error::Error>> {
+            let (header, _) = b.split_header::<u32>()?;
+            Ok(*header)
+        }
+        let err = header(Bytes::from(vec![1u8])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "bytes have incompatible layout for the target type"
+        );
+        assert_eq!(header(Bytes::from(vec![5u32])).unwrap(), 5);
+        assert_eq!(
+            PackError::Alignment.to_string(),
+            "bytes are not aligned for the target type"
+        );
+    }
+
+    #[test]
+    fn cast() {
+        let p = Packed::copy_from(&[1u16, 2]);
+        let owner = p.bytes();
+        let cast = p.cast::<u32>().unwrap();
+        assert!(cast.bytes().ptr_eq(&owner));
+        assert_eq!(cast.cast::<u64>().err(), Some(PackError::BadLayout));
+    }
+
+    #[derive(zerocopy::FromZeroes, zerocopy::FromBytes, zerocopy::AsBytes)]
+    #[repr(C)]
+    struct FrameHeader {
+        kind: u32,
+        payload_len: u32,
+    }
+
+    #[test]
+    fn map() {
+        let header = Packed::copy_from(&FrameHeader {
+            kind: 1,
+            payload_len: 42,
+        });
+        let len = header.map(|h| &h.payload_len).unwrap();
+        drop(header);
+        assert_eq!(*len, 42);
+
+        let header = Packed::copy_from(&FrameHeader {
+            kind: 1,
+            payload_len: 42,
+        });
+        static OUTSIDE: u32 = 0;
+        assert!(header.map(|_| &OUTSIDE).is_none());
+    }
+
+    #[test]
+    fn split_header() {
+        let b = Bytes::from(vec![1u32, 2, 3]);
+        let (header, payload) = b.split_header::<u32>().unwrap();
+        assert_eq!(*header, 1);
+        assert_eq!(payload.len(), 8);
+
+        let b = Bytes::from(vec![1u8, 2]);
+        assert_eq!(b.split_header::<u32>().err(), Some(PackError::BadLayout));
+    }
 }
 
 #[cfg(test)]
@@ -1,9 +1,24 @@
-use std::{fmt::Debug, hash::Hash, marker::PhantomData, ops::Deref, sync::Arc};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Bound, Deref, RangeBounds},
+    sync::Arc,
+};
 
-use super::PackError;
+use super::{PackError, Packed};
 use crate::{bytes::ByteOwner, ByteSource, Bytes};
 use zerocopy::{AsBytes, FromBytes};
 
+/// A typed handle to a `[T]` stored in [Bytes].
+///
+/// Like [Packed](super::Packed) this requires `T: FromBytes`, so slices
+/// of types with invalid bit patterns can't be constructed:
+///
+/// ```compile_fail
+/// use anybytes::{Bytes, PackedSlice};
+/// let p: PackedSlice<bool> = Bytes::from(vec![0u8, 2]).try_into().unwrap();
+/// ```
 pub struct PackedSlice<T> {
     bytes: Bytes,
     _type: PhantomData<T>,
@@ -28,6 +43,92 @@ impl<T> PackedSlice<T> {
     pub fn bytes(&self) -> Bytes {
         self.bytes.clone()
     }
+
+    /// Reinterprets the underlying bytes as a `[U]`, keeping the owner.
+    ///
+    /// The bytes are validated for `U` again, so this fails like
+    /// [TryFrom] if their size or alignment doesn't fit.
+    pub fn cast<U>(self) -> Result<PackedSlice<U>, PackError>
+    where
+        U: FromBytes,
+    {
+        self.bytes.try_into()
+    }
+}
+
+impl<T> PackedSlice<T>
+where
+    T: FromBytes,
+{
+    /// Returns a borrowing iterator over the elements.
+    ///
+    /// Unlike iterating over owning sub-slices this doesn't touch
+    /// the reference count of the owner.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.deref().iter()
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.deref().get(index)
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.deref().len()
+    }
+
+    /// Returns `true` if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.deref().is_empty()
+    }
+
+    /// Returns the element at `index` as a [Packed] sharing the same owner,
+    /// so it stays valid after `self` is dropped.
+    pub fn get_packed(&self, index: usize) -> Option<Packed<T>> {
+        let slice = self.get_slice(index..index.checked_add(1)?)?;
+        slice.bytes.try_into().ok()
+    }
+
+    /// Returns an iterator over the elements as [Packed] handles
+    /// sharing the same owner.
+    pub fn iter_packed(&self) -> impl Iterator<Item = Packed<T>> + '_ {
+        (0..self.len()).map(|i| self.get_packed(i).expect("index is in bounds"))
+    }
+
+    /// Returns the elements in `range` as a new [PackedSlice]
+    /// sharing the same owner, or `None` if `range` is out of bounds.
+    pub fn get_slice(&self, range: impl RangeBounds<usize>) -> Option<Self> {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i.checked_add(1)?,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len(),
+        };
+        if start > end || end > self.len() {
+            return None;
+        }
+        let size = size_of::<T>();
+        Some(PackedSlice {
+            bytes: self.bytes.slice(start * size..end * size),
+            _type: PhantomData,
+        })
+    }
+
+    /// Returns the elements in `range` as a new [PackedSlice]
+    /// sharing the same owner.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, like slice indexing.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        let len = self.len();
+        self.get_slice(range)
+            .unwrap_or_else(|| panic!("range out of bounds for slice of length {len}"))
+    }
 }
 
 impl<T> Clone for PackedSlice<T> {
@@ -91,9 +192,11 @@ where
 {
     type Error = PackError;
 
+    #[allow(clippy::manual_is_multiple_of)]
     fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
         if <T as FromBytes>::slice_from(&bytes).is_none() {
-            Err(PackError::BadLayout)
+            let size_ok = size_of::<T>() != 0 && bytes.len() % size_of::<T>() == 0;
+            Err(PackError::for_data::<T>(&bytes, size_ok))
         } else {
             Ok(PackedSlice {
                 bytes,
@@ -109,9 +212,11 @@ where
 {
     type Error = PackError;
 
+    #[allow(clippy::manual_is_multiple_of)]
     fn try_from(bytes: &Bytes) -> Result<Self, Self::Error> {
         if <T as FromBytes>::slice_from(bytes).is_none() {
-            Err(PackError::BadLayout)
+            let size_ok = size_of::<T>() != 0 && bytes.len() % size_of::<T>() == 0;
+            Err(PackError::for_data::<T>(bytes, size_ok))
         } else {
             Ok(PackedSlice {
                 bytes: bytes.clone(),
@@ -153,6 +258,33 @@ where
 
 impl<T> Eq for PackedSlice<T> where T: FromBytes + std::cmp::Eq {}
 
+impl<T> PartialEq<[T]> for PackedSlice<T>
+where
+    T: FromBytes + std::cmp::PartialEq,
+{
+    fn eq(&self, other: &[T]) -> bool {
+        self.deref() == other
+    }
+}
+
+impl<T> PartialOrd for PackedSlice<T>
+where
+    T: FromBytes + std::cmp::PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.deref().partial_cmp(other.deref())
+    }
+}
+
+impl<T> PartialOrd<[T]> for PackedSlice<T>
+where
+    T: FromBytes + std::cmp::PartialOrd,
+{
+    fn partial_cmp(&self, other: &[T]) -> Option<std::cmp::Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+
 impl<T> Hash for PackedSlice<T>
 where
     T: FromBytes + Hash,
@@ -165,7 +297,8 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::PackedSlice;
+    use crate::packed::PackError;
+    use crate::{Bytes, PackedSlice};
 
     #[test]
     fn roundtrip_copy() {
@@ -183,4 +316,98 @@ mod test {
         let r: &[_] = &p;
         assert_eq!(v.as_slice(), r)
     }
+
+    #[test]
+    fn compare_with_slice() {
+        let p = PackedSlice::copy_from(&[1usize, 2, 3]);
+        assert!(p == [1usize, 2, 3][..]);
+        assert!(p < [1usize, 2, 4][..]);
+        assert!(p > [1usize, 2][..]);
+        assert!(p < PackedSlice::copy_from(&[2usize]));
+    }
+
+    #[test]
+    fn iter() {
+        let v: Vec<usize> = vec![1, 2, 3, 4];
+        let p: PackedSlice<_> = v.clone().into();
+        let r: Vec<usize> = p.iter().copied().collect();
+        assert_eq!(v, r)
+    }
+
+    #[test]
+    fn indexing() {
+        let p = PackedSlice::copy_from(&[1u32, 2, 3, 4]);
+        assert_eq!(p.len(), 4);
+        assert_eq!(p.get(0), Some(&1));
+        assert_eq!(p.get(3), Some(&4));
+        assert_eq!(p.get(4), None);
+
+        let sub = p.slice(1..3);
+        assert_eq!(&sub[..], &[2, 3]);
+        assert!(sub.bytes().owner_ptr_eq(&p.bytes()));
+        assert_eq!(&p.slice(..=1)[..], &[1, 2]);
+
+        let empty = p.slice(4..);
+        assert!(empty.is_empty());
+        assert_eq!(empty.get(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_out_of_bounds() {
+        let p = PackedSlice::copy_from(&[1u32, 2]);
+        p.slice(1..3);
+    }
+
+    #[test]
+    fn packed_elements_share_owner() {
+        let p = PackedSlice::copy_from(&[1u32, 2, 3]);
+        let second = p.get_packed(1).unwrap();
+        let elems: Vec<_> = p.iter_packed().collect();
+        let tail = p.get_slice(1..).unwrap();
+        assert!(p.get_packed(3).is_none());
+        assert!(p.get_slice(2..4).is_none());
+        assert!(p.get_slice(3..=usize::MAX).is_none());
+        let owner = p.bytes();
+        drop(p);
+
+        assert_eq!(*second, 2);
+        assert!(second.bytes().owner_ptr_eq(&owner));
+        assert_eq!(elems.iter().map(|e| **e).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(&tail[..], &[2, 3]);
+        assert!(tail.bytes().owner_ptr_eq(&owner));
+    }
+
+    #[test]
+    fn cast() {
+        let bytes = Bytes::from(vec![1u32, 2, 3]);
+        let p: PackedSlice<u8> = bytes.clone().try_into().unwrap();
+        let words = p.clone().cast::<u32>().unwrap();
+        assert_eq!(&words[..], &[1, 2, 3]);
+        assert!(words.bytes().ptr_eq(&bytes));
+
+        let short = p.slice(..6).cast::<u32>();
+        assert_eq!(short.err(), Some(PackError::BadLayout));
+        let misaligned = p.slice(1..9).cast::<u32>();
+        assert_eq!(misaligned.err(), Some(PackError::Alignment));
+    }
+
+    #[test]
+    fn align_to() {
+        let bytes = Bytes::from(vec![0u32; 4]);
+        let misaligned = bytes.slice(1..15);
+        let (head, body, tail) = misaligned.align_to::<u32>();
+        assert_eq!(head.len(), 3);
+        assert_eq!(body.len(), 2);
+        assert_eq!(tail.len(), 3);
+        assert!(body.bytes().ptr_eq(&bytes.slice(4..12)));
+
+        let (head, body, tail) = bytes.align_to::<u32>();
+        assert!(head.is_empty() && tail.is_empty());
+        assert_eq!(body.len(), 4);
+
+        let (head, body, tail) = bytes.slice(1..3).align_to::<u32>();
+        assert_eq!(head.len() + tail.len(), 2);
+        assert!(body.is_empty());
+    }
 }
@@ -1,7 +1,8 @@
 use std::{fmt::Debug, hash::Hash, ops::Deref, str::Utf8Error, sync::Arc};
 
-use crate::{bytes::ByteOwner, ByteSource, Bytes};
+use crate::{bytes::ByteOwner, ByteSource, Bytes, TextOwner};
 
+#[derive(Default)]
 pub struct PackedStr {
     bytes: Bytes,
 }
@@ -14,6 +15,14 @@ impl PackedStr {
         }
     }
 
+    /// Creates a `PackedStr` from a source that guarantees valid UTF-8,
+    /// skipping validation.
+    pub fn from_text_source(source: impl TextOwner) -> Self {
+        PackedStr {
+            bytes: Bytes::from_source(source),
+        }
+    }
+
     pub fn unwrap(self) -> Bytes {
         self.bytes
     }
@@ -54,14 +63,6 @@ impl std::fmt::Debug for PackedStr {
     }
 }
 
-impl Default for PackedStr {
-    fn default() -> Self {
-        Self {
-            bytes: Default::default(),
-        }
-    }
-}
-
 impl PartialEq for PackedStr {
     fn eq(&self, other: &Self) -> bool {
         let self_slice = self.deref();
@@ -120,12 +121,14 @@ impl TryFrom<&Bytes> for PackedStr {
 
 #[cfg(test)]
 mod test {
-    use crate::PackedStr;
+    use std::sync::Arc;
+
+    use crate::{Bytes, PackedStr};
 
     #[test]
     fn roundtrip_copy() {
         let v = "hello world!";
-        let p = PackedStr::copy_from(&v);
+        let p = PackedStr::copy_from(v);
         let pr: &str = p.as_ref();
         assert_eq!(v, pr)
     }
@@ -137,4 +140,28 @@ mod test {
         let r: &str = &p;
         assert_eq!(&v, r)
     }
+
+    #[test]
+    fn from_text_source() {
+        let expected = "hello world!";
+        let sources = [
+            PackedStr::from_text_source(expected.to_string()),
+            PackedStr::from_text_source(expected),
+            PackedStr::from_text_source(Box::<str>::from(expected)),
+            PackedStr::from_text_source(Arc::<str>::from(expected)),
+        ];
+        for p in sources {
+            assert_eq!(&*p, expected);
+            assert_eq!(Some(&*p), PackedStr::try_from(p.bytes()).ok().as_deref());
+        }
+    }
+
+    #[test]
+    fn from_bytes() {
+        let b = Bytes::from(b"hello".to_vec());
+        assert_eq!(&*b.try_into_packed_str().unwrap(), "hello");
+        let b = Bytes::from(b"\xc3\x28".to_vec());
+        assert!(b.try_into_packed_str().is_err());
+        assert_eq!(&*Bytes::empty().try_into_packed_str().unwrap(), "");
+    }
 }
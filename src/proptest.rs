@@ -0,0 +1,107 @@
+//! [proptest](https://docs.rs/proptest) strategies for generating [Bytes].
+//!
+//! The generated values vary both the owner type backing the bytes
+//! and the offset of the referenced range within that owner, so code
+//! consuming [Bytes] gets exercised across owner- and offset-dependent paths.
+
+use ::proptest::collection::{vec, SizeRange};
+use ::proptest::prelude::*;
+
+use crate::Bytes;
+
+static STATIC_BYTES: [u8; 256] = {
+    let mut bytes = [0u8; 256];
+    let mut i = 0;
+    while i < bytes.len() {
+        bytes[i] = i as u8;
+        i += 1;
+    }
+    bytes
+};
+
+/// Generates [Bytes] backed by a `Vec<u8>`, a `Box<[u8]>` or static memory.
+pub fn any_bytes() -> impl Strategy<Value = Bytes> {
+    prop_oneof![
+        vec(any::<u8>(), 0..256).prop_map(Bytes::from_source),
+        vec(any::<u8>(), 0..256).prop_map(|v| Bytes::from_source(v.into_boxed_slice())),
+        (0..=STATIC_BYTES.len())
+            .prop_flat_map(|end| (0..=end, Just(end)))
+            .prop_map(|(start, end)| {
+                let slice: &'static [u8] = &STATIC_BYTES[start..end];
+                Bytes::from_source(slice)
+            }),
+    ]
+}
+
+/// Like [any_bytes] but additionally slices off random leading
+/// and trailing ranges, so the data doesn't start at the owner's start.
+pub fn any_bytes_sliced() -> impl Strategy<Value = Bytes> {
+    any_bytes()
+        .prop_flat_map(|bytes| {
+            let len = bytes.len();
+            (Just(bytes), 0..=len)
+                .prop_flat_map(move |(bytes, end)| (Just(bytes), 0..=end, Just(end)))
+        })
+        .prop_map(|(bytes, start, end)| bytes.slice(start..end))
+}
+
+/// Generates [Bytes] whose length lies in `len`, backed by a `Vec<u8>`
+/// that often extends beyond the referenced range on either side.
+///
+/// Unlike [any_bytes_sliced] this keeps control over the length of the
+/// data, e.g. `bytes_strategy(0..64)`.
+pub fn bytes_strategy(len: impl Into<SizeRange>) -> impl Strategy<Value = Bytes> {
+    let padding = prop_oneof![Just(0usize), 1usize..16];
+    (vec(any::<u8>(), len), padding.clone(), padding).prop_map(|(data, front, back)| {
+        let len = data.len();
+        let mut owner = vec![0u8; front];
+        owner.extend_from_slice(&data);
+        owner.resize(front + len + back, 0);
+        Bytes::from_source(owner).slice(front..front + len)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::prelude::*;
+
+    use ::proptest::strategy::ValueTree;
+    use ::proptest::test_runner::TestRunner;
+
+    use super::{any_bytes, any_bytes_sliced, bytes_strategy};
+
+    proptest! {
+        #[test]
+        fn generated_bytes_are_valid(bytes in any_bytes()) {
+            bytes.validate_invariants();
+            prop_assert_eq!(bytes.to_vec(), bytes.clone().into_vec());
+        }
+
+        #[test]
+        fn generated_slices_are_valid(bytes in any_bytes_sliced()) {
+            bytes.validate_invariants();
+            prop_assert_eq!(bytes.to_vec(), bytes.clone().into_vec());
+        }
+
+        #[test]
+        fn bytes_strategy_respects_len(bytes in bytes_strategy(3..=5)) {
+            bytes.validate_invariants();
+            prop_assert!((3..=5).contains(&bytes.len()));
+        }
+    }
+
+    #[test]
+    fn bytes_strategy_covers_sliced_and_empty() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = bytes_strategy(0..8);
+        let (mut sliced, mut empty) = (false, false);
+        for _ in 0..256 {
+            let bytes = strategy.new_tree(&mut runner).unwrap().current();
+            assert!(bytes.len() < 8);
+            let owner = bytes.clone().downcast_to_owner::<Vec<u8>>().unwrap();
+            sliced |= owner.len() != bytes.len();
+            empty |= bytes.is_empty();
+        }
+        assert!(sliced && empty);
+    }
+}
@@ -1,8 +1,56 @@
-use pyo3::{prelude::*, ffi, PyResult};
+use pyo3::{
+    buffer::PyBuffer,
+    exceptions::{PyBufferError, PyIndexError},
+    ffi,
+    prelude::*,
+    types::{PyBytesMethods, PySlice, PySliceMethods},
+    PyResult,
+};
 use std::os::raw::c_int;
 
 use crate::Bytes;
 
+impl Bytes {
+    /// Creates `Bytes` from any Python object supporting the buffer
+    /// protocol with byte-sized items, like `bytes`, `bytearray`,
+    /// `memoryview` or numpy arrays.
+    ///
+    /// Python `bytes` objects are shared without copying and the returned
+    /// `Bytes` keep them alive. Everything else is copied: a buffer that
+    /// reports itself as read-only may still be a view of mutable memory,
+    /// like `memoryview(bytearray).toreadonly()`.
+    /// Non-contiguous buffers raise `BufferError`.
+    pub fn from_py_buffer(obj: &Bound<'_, PyAny>) -> PyResult<Bytes> {
+        if let Ok(bytes) = obj.downcast_exact::<pyo3::types::PyBytes>() {
+            return Ok(Bytes::from_source(bytes.clone()));
+        }
+        let buffer = PyBuffer::<u8>::get(obj)?;
+        if !buffer.is_c_contiguous() {
+            return Err(PyBufferError::new_err("buffer is not C-contiguous"));
+        }
+        if buffer.len_bytes() == 0 {
+            return Ok(Bytes::empty());
+        }
+        Ok(Bytes::from_source(buffer.to_vec(obj.py())?))
+    }
+
+    /// Hands the data to Python without copying.
+    ///
+    /// If the `Bytes` span a whole Python `bytes` object they were created
+    /// from, that object is returned. Otherwise the data is wrapped in the
+    /// Python `Bytes` class, which exposes it through the buffer protocol,
+    /// so `memoryview` or `numpy.frombuffer` can read it directly.
+    pub fn into_py_bytes(self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        if let Some(owner) = self.clone().downcast_to_owner::<Py<pyo3::types::PyBytes>>() {
+            let data = PyBytesMethods::as_bytes(owner.bind(py));
+            if std::ptr::eq(data, self.as_slice()) {
+                return Ok(owner.clone_ref(py).into_any());
+            }
+        }
+        Ok(Py::new(py, PyBytes { bytes: self })?.into_any())
+    }
+}
+
 #[pyclass(name = "Bytes")]
 pub struct PyBytes {
     bytes: Bytes
@@ -10,6 +58,13 @@ pub struct PyBytes {
 
 #[pymethods]
 impl PyBytes {
+    #[new]
+    fn new(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyBytes {
+            bytes: Bytes::from_py_buffer(obj)?,
+        })
+    }
+
     unsafe fn __getbuffer__(
         slf: PyRefMut<Self>,
         view: *mut ffi::Py_buffer,
@@ -29,4 +84,190 @@ impl PyBytes {
         }
         Ok(())
     }
-}
\ No newline at end of file
+
+    fn __len__(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Copies the data into a new Python `bytes` object.
+    fn tobytes(&self, py: Python<'_>) -> Py<pyo3::types::PyBytes> {
+        pyo3::types::PyBytes::new(py, self.bytes.as_slice()).unbind()
+    }
+
+    /// Copies the data into a new Python `bytes` object, see `tobytes`.
+    fn __bytes__(&self, py: Python<'_>) -> Py<pyo3::types::PyBytes> {
+        self.tobytes(py)
+    }
+
+    /// Returns the byte at an integer index as an `int`, or the bytes
+    /// selected by a slice as a new `Bytes`.
+    ///
+    /// Contiguous slices share the underlying data, stepped slices copy.
+    fn __getitem__(&self, py: Python<'_>, index: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        if let Ok(slice) = index.downcast::<PySlice>() {
+            let indices = slice.indices(self.bytes.len() as isize)?;
+            let bytes = if indices.slicelength == 0 {
+                Bytes::empty()
+            } else if indices.step == 1 {
+                let start = indices.start as usize;
+                self.bytes.slice(start..start + indices.slicelength)
+            } else {
+                let data: Vec<u8> = (0..indices.slicelength as isize)
+                    .map(|i| self.bytes[(indices.start + i * indices.step) as usize])
+                    .collect();
+                Bytes::from_source(data)
+            };
+            return Ok(Py::new(py, PyBytes { bytes })?.into_any());
+        }
+
+        let i: isize = index.extract()?;
+        let len = self.bytes.len() as isize;
+        let i = if i < 0 { i + len } else { i };
+        if !(0..len).contains(&i) {
+            return Err(PyIndexError::new_err("index out of range"));
+        }
+        Ok(self.bytes[i as usize]
+            .into_pyobject(py)?
+            .into_any()
+            .unbind())
+    }
+}
+
+impl From<Bytes> for PyBytes {
+    fn from(bytes: Bytes) -> Self {
+        PyBytes { bytes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::prelude::*;
+
+    use super::PyBytes;
+    use crate::Bytes;
+
+    #[test]
+    fn len_and_getitem() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let b = Py::new(py, PyBytes::from(Bytes::from(b"abcdef".to_vec()))).unwrap();
+            let b = b.bind(py);
+            assert_eq!(b.len().unwrap(), 6);
+            assert_eq!(b.get_item(0).unwrap().extract::<u8>().unwrap(), b'a');
+            assert_eq!(b.get_item(-1).unwrap().extract::<u8>().unwrap(), b'f');
+            assert!(b.get_item(6).is_err());
+            assert!(b.get_item(-7).is_err());
+
+            let locals = pyo3::types::PyDict::new(py);
+            locals.set_item("b", b).unwrap();
+            let eval = |code: &str| {
+                let code = std::ffi::CString::new(code).unwrap();
+                py.eval(&code, None, Some(&locals)).unwrap()
+            };
+            assert_eq!(eval("bytes(b[1:4])").extract::<Vec<u8>>().unwrap(), b"bcd");
+            assert_eq!(eval("bytes(b[-2:])").extract::<Vec<u8>>().unwrap(), b"ef");
+            assert_eq!(eval("bytes(b[::2])").extract::<Vec<u8>>().unwrap(), b"ace");
+            assert_eq!(eval("bytes(b[::-1])").extract::<Vec<u8>>().unwrap(), b"fedcba");
+            assert_eq!(eval("len(b[4:1])").extract::<usize>().unwrap(), 0);
+
+            let sub = eval("b[1:4]");
+            let sub = sub.downcast::<PyBytes>().unwrap().borrow();
+            assert!(sub.bytes.owner_ptr_eq(&b.borrow().bytes));
+        });
+    }
+
+    #[test]
+    fn from_py_buffer() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let eval = |code: &str| {
+                let code = std::ffi::CString::new(code).unwrap();
+                py.eval(&code, None, None).unwrap()
+            };
+
+            let source = eval("bytearray(b'abcdef')");
+            let b = Bytes::from_py_buffer(&source).unwrap();
+            assert_eq!(b, &b"abcdef"[..]);
+            assert!(b.downcast_to_owner::<Vec<u8>>().is_some());
+
+            let source = eval("b'abcdef'");
+            let b = Bytes::from_py_buffer(&source).unwrap();
+            assert_eq!(b, &b"abcdef"[..]);
+            assert!(b.downcast_to_owner::<Py<pyo3::types::PyBytes>>().is_some());
+
+            let view = eval("memoryview(b'abcdef')[1:4]");
+            let b = Bytes::from_py_buffer(&view).unwrap();
+            assert_eq!(b, &b"bcd"[..]);
+            assert!(b.downcast_to_owner::<Vec<u8>>().is_some());
+
+            let strided = eval("memoryview(b'abcdef')[::2]");
+            let err = Bytes::from_py_buffer(&strided).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyBufferError>(py));
+
+            let wide = eval("memoryview(b'abcd').cast('I')");
+            assert!(Bytes::from_py_buffer(&wide).is_err());
+
+            let cls = py.get_type::<PyBytes>();
+            let wrapped = cls.call1((eval("b'xyz'"),)).unwrap();
+            assert_eq!(wrapped.len().unwrap(), 3);
+        });
+    }
+
+    #[test]
+    fn from_py_buffer_copies_readonly_views() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let locals = pyo3::types::PyDict::new(py);
+            let run = |code: &str| {
+                let code = std::ffi::CString::new(code).unwrap();
+                py.run(&code, None, Some(&locals)).unwrap();
+            };
+            run("source = bytearray(b'abc'); view = memoryview(source).toreadonly()");
+            let view = locals.get_item("view").unwrap().unwrap();
+            assert!(pyo3::buffer::PyBuffer::<u8>::get(&view).unwrap().readonly());
+
+            let b = Bytes::from_py_buffer(&view).unwrap();
+            assert!(b.clone().downcast_to_owner::<Vec<u8>>().is_some());
+            run("source[0] = ord('x')");
+            assert_eq!(b, &b"abc"[..]);
+        });
+    }
+
+    #[test]
+    fn tobytes() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let b = Py::new(py, PyBytes::from(Bytes::from(b"abc".to_vec()))).unwrap();
+            let b = b.bind(py);
+            let copy = b.call_method0("tobytes").unwrap();
+            assert!(copy.is_instance_of::<pyo3::types::PyBytes>());
+            assert_eq!(copy.extract::<Vec<u8>>().unwrap(), b"abc");
+
+            let builtins = py.import("builtins").unwrap();
+            let copy = builtins.getattr("bytes").unwrap().call1((b,)).unwrap();
+            assert_eq!(copy.extract::<Vec<u8>>().unwrap(), b"abc");
+        });
+    }
+
+    #[test]
+    fn into_py_bytes() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let b = Bytes::from(b"abcdef".to_vec()).slice(1..);
+            let ptr = b.as_ptr();
+            let obj = b.into_py_bytes(py).unwrap();
+            let buffer = pyo3::buffer::PyBuffer::<u8>::get(obj.bind(py)).unwrap();
+            assert_eq!(buffer.buf_ptr() as *const u8, ptr);
+            assert_eq!(buffer.to_vec(py).unwrap(), b"bcdef");
+            drop(buffer);
+
+            let source = pyo3::types::PyBytes::new(py, b"xyz");
+            let b = Bytes::from_source(source.clone());
+            let obj = b.clone().into_py_bytes(py).unwrap();
+            assert!(obj.bind(py).is(&source));
+
+            let obj = b.slice(1..).into_py_bytes(py).unwrap();
+            assert!(obj.bind(py).downcast::<PyBytes>().is_ok());
+        });
+    }
+}
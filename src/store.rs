@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use crate::Bytes;
+
+/// A content-addressed store of [Bytes] keyed by their BLAKE3 digest.
+///
+/// Storing identical content twice keeps only the first `Bytes`.
+///
+/// # Examples
+///
+/// ```
+/// use anybytes::{Bytes, ContentStore};
+/// let mut store = ContentStore::new();
+/// let digest = store.put(Bytes::from(b"hello".to_vec()));
+/// assert_eq!(store.get(&digest).unwrap(), b"hello");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ContentStore {
+    entries: HashMap<[u8; 32], Bytes>,
+}
+
+impl ContentStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `bytes` and returns their digest.
+    ///
+    /// If identical content is already stored the existing entry is kept.
+    pub fn put(&mut self, bytes: Bytes) -> [u8; 32] {
+        let digest: [u8; 32] = blake3::hash(&bytes).into();
+        self.entries.entry(digest).or_insert(bytes);
+        digest
+    }
+
+    /// Returns the stored `Bytes` for `digest`, if any.
+    pub fn get(&self, digest: &[u8; 32]) -> Option<Bytes> {
+        self.entries.get(digest).cloned()
+    }
+
+    /// Returns the number of distinct entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the store holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContentStore;
+    use crate::Bytes;
+
+    #[test]
+    fn deduplicates() {
+        let mut store = ContentStore::new();
+        let a = Bytes::from(b"abcd".to_vec());
+        let b = Bytes::from(b"abcd".to_vec());
+        let da = store.put(a.clone());
+        let db = store.put(b);
+        assert_eq!(da, db);
+        assert_eq!(store.len(), 1);
+        assert!(store.get(&da).unwrap().ptr_eq(&a));
+
+        let dc = store.put(Bytes::from(b"efgh".to_vec()));
+        assert_ne!(da, dc);
+        assert_eq!(store.len(), 2);
+        assert!(store.get(&[0; 32]).is_none());
+    }
+}
@@ -6,9 +6,45 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::collections::VecDeque;
+
 use quickcheck::quickcheck;
 
-use crate::Bytes;
+use crate::{Bytes, CopyError, WeakBytes};
+
+fn encode_uleb128(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn encode_sleb128(mut value: i64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+// Serializes tests that create memory maps so the global
+// mapping statistics can be checked exactly.
+#[cfg(feature = "mmap")]
+static MMAP_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("anybytes-{}-{}", std::process::id(), name))
+}
 
 quickcheck! {
     fn test_shallow_clone(v: Vec<u8>) -> bool {
@@ -17,11 +53,130 @@ quickcheck! {
         a == b && a.as_ptr() == b.as_ptr()
     }
 
+    #[allow(clippy::op_ref)]
     fn test_shallow_slice(v: Vec<u8>) -> bool {
         let a: Bytes = v.into();
         let b: Bytes = a.slice(..a.len() / 2);
         b == &a[..b.len()] && (b.is_empty() || a.as_ptr() == b.as_ptr())
     }
+
+    fn test_split(v: Vec<u8>, delim: u8) -> bool {
+        let expected: Vec<Vec<u8>> = v.split(|&b| b == delim).map(|s| s.to_vec()).collect();
+        let b: Bytes = v.into();
+        let actual: Vec<Vec<u8>> = b.split(delim).map(|s| s.to_vec()).collect();
+        expected == actual
+    }
+
+    fn test_splitn(v: Vec<u8>, n: u8, delim: u8) -> bool {
+        let n = (n % 8) as usize;
+        let expected: Vec<Vec<u8>> = v.splitn(n, |&b| b == delim).map(|s| s.to_vec()).collect();
+        let b: Bytes = v.into();
+        let actual: Vec<Vec<u8>> = b.splitn(n, delim).map(|s| s.to_vec()).collect();
+        expected == actual
+    }
+
+    fn test_find(v: Vec<u8>, needle: Vec<u8>) -> bool {
+        let expected = if needle.is_empty() {
+            Some(0)
+        } else {
+            v.windows(needle.len()).position(|w| w == &needle[..])
+        };
+        let b: Bytes = v.into();
+        b.find(&needle) == expected && b.contains(&needle) == expected.is_some()
+    }
+
+    fn test_varint_u64_roundtrip(values: Vec<u64>, tail: Vec<u8>) -> bool {
+        let mut v = Vec::new();
+        for &value in &values {
+            encode_uleb128(value, &mut v);
+        }
+        v.extend_from_slice(&tail);
+        let mut b: Bytes = v.into();
+        values.iter().all(|&value| b.read_varint_u64() == Some(value)) && b == tail
+    }
+
+    fn test_varint_i64_roundtrip(values: Vec<i64>, tail: Vec<u8>) -> bool {
+        let mut v = Vec::new();
+        for &value in &values {
+            encode_sleb128(value, &mut v);
+        }
+        v.extend_from_slice(&tail);
+        let mut b: Bytes = v.into();
+        values.iter().all(|&value| b.read_varint_i64() == Some(value)) && b == tail
+    }
+
+    fn test_diff_ranges(a: Vec<u8>, b: Vec<u8>) -> bool {
+        let len = a.len().min(b.len());
+        let mut expected = Vec::new();
+        let mut i = 0;
+        while i < len {
+            if a[i] != b[i] {
+                let start = i;
+                while i < len && a[i] != b[i] {
+                    i += 1;
+                }
+                expected.push(start..i);
+            } else {
+                i += 1;
+            }
+        }
+        let bytes: Bytes = a.into();
+        bytes.diff_ranges(&b) == expected
+    }
+
+    fn test_content_eq(a: Vec<u8>, b: Vec<u8>) -> bool {
+        let expected = a == b;
+        let bytes: Bytes = a.into();
+        bytes.content_eq(&b) == expected && (bytes == b) == expected
+    }
+
+    fn test_rsplit(v: Vec<u8>, delim: u8) -> bool {
+        let expected: Vec<Vec<u8>> = v.rsplit(|&b| b == delim).map(|s| s.to_vec()).collect();
+        let b: Bytes = v.into();
+        let actual: Vec<Vec<u8>> = b.rsplit(delim).map(|s| s.to_vec()).collect();
+        expected == actual
+    }
+
+    fn test_lines(s: String) -> bool {
+        let b = Bytes::from(s.clone().into_bytes());
+        b.lines().map(|l| l.to_vec()).eq(s.lines().map(|l| l.as_bytes().to_vec()))
+    }
+
+    fn test_lines_with_endings(parts: Vec<(String, bool)>, trailing: bool) -> bool {
+        let mut s = String::new();
+        for (i, (part, crlf)) in parts.iter().enumerate() {
+            s.push_str(part);
+            if i + 1 < parts.len() || trailing {
+                s.push_str(if *crlf { "\r\n" } else { "\n" });
+            }
+        }
+        let b = Bytes::from(s.clone().into_bytes());
+        b.lines().map(|l| l.to_vec()).eq(s.lines().map(|l| l.as_bytes().to_vec()))
+    }
+
+    fn test_hex_roundtrip(v: Vec<u8>) -> bool {
+        let b: Bytes = v.into();
+        Bytes::from_hex(&b.to_hex()).unwrap() == b
+    }
+
+    fn test_chunks(v: Vec<u8>, size: u8) -> bool {
+        let size = size as usize + 1;
+        let b: Bytes = v.clone().into();
+        let chunks = b.chunks(size).map(|c| c.to_vec()).eq(v.chunks(size).map(|c| c.to_vec()));
+        let rchunks = b.rchunks(size).map(|c| c.to_vec()).eq(v.rchunks(size).map(|c| c.to_vec()));
+        let exact = b.chunks_exact(size);
+        let remainder = exact.remainder() == v.chunks_exact(size).remainder();
+        let exact = exact.map(|c| c.to_vec()).eq(v.chunks_exact(size).map(|c| c.to_vec()));
+        chunks && rchunks && exact && remainder
+    }
+}
+
+#[cfg(feature = "base64")]
+quickcheck! {
+    fn test_base64_roundtrip(v: Vec<u8>) -> bool {
+        let b: Bytes = v.into();
+        Bytes::from_base64(&b.to_base64()).unwrap() == b
+    }
 }
 
 #[test]
@@ -61,3 +216,662 @@ fn test_downgrade_upgrade() {
     let b3 = wb.upgrade();
     assert!(b3.is_none());
 }
+
+#[test]
+fn test_with_nul_terminator() {
+    let b = Bytes::from(b"abc\0".to_vec());
+    let n = b.with_nul_terminator();
+    assert_eq!(b.as_ptr(), n.as_ptr());
+    assert_eq!(n.as_c_str().unwrap().to_bytes(), b"abc");
+
+    let b = Bytes::from(b"abc".to_vec());
+    let n = b.with_nul_terminator();
+    assert_eq!(n, b"abc\0");
+    assert!(b.as_c_str().is_none());
+
+    let b = Bytes::from(b"a\0bc\0".to_vec());
+    assert!(b.as_c_str().is_none());
+}
+
+#[test]
+fn test_split_trailing_delimiter() {
+    let b = Bytes::from(b"a\nb\n".to_vec());
+    let parts: Vec<Bytes> = b.split(b'\n').collect();
+    assert_eq!(parts, [&b"a"[..], &b"b"[..], &b""[..]]);
+}
+
+#[test]
+fn test_chunks_share_owner() {
+    let b = Bytes::from(b"abcdefg".to_vec());
+    let chunks: Vec<Bytes> = b.chunks(3).collect();
+    assert_eq!(chunks, [&b"abc"[..], &b"def"[..], &b"g"[..]]);
+    assert!(chunks.iter().all(|c| c.owner_ptr_eq(&b)));
+    let exact = b.chunks_exact(3);
+    assert_eq!(exact.remainder(), &b"g"[..]);
+    assert_eq!(exact.count(), 2);
+}
+
+#[test]
+#[should_panic]
+fn test_chunks_zero_size() {
+    Bytes::from(b"abc".to_vec()).chunks(0);
+}
+
+#[test]
+fn test_trim_ascii() {
+    let b = Bytes::from(b" \t\x0ca b\r\n".to_vec());
+    assert_eq!(b.trim_ascii(), &b"a b"[..]);
+    assert_eq!(b.trim_ascii_start(), &b"a b\r\n"[..]);
+    assert_eq!(b.trim_ascii_end(), &b" \t\x0ca b"[..]);
+    assert!(b.trim_ascii().owner_ptr_eq(&b));
+
+    assert!(Bytes::from(b" \n\t".to_vec()).trim_ascii().is_empty());
+    assert!(Bytes::empty().trim_ascii().is_empty());
+}
+
+#[test]
+fn test_drain_front_of_deque() {
+    let mut d: VecDeque<u8> = VecDeque::with_capacity(4);
+    d.extend([1, 2, 3]);
+    d.pop_front();
+    d.extend([4, 5]);
+    let b = Bytes::drain_front_of_deque(&mut d, 3);
+    assert_eq!(b, [2, 3, 4]);
+    assert_eq!(d, [5]);
+    let b = Bytes::drain_front_of_deque(&mut d, 10);
+    assert_eq!(b, [5]);
+    assert!(d.is_empty());
+}
+
+#[test]
+fn test_find_rfind() {
+    let b = Bytes::from(b"aaaa".to_vec());
+    assert_eq!(b.find(b"aa"), Some(0));
+    assert_eq!(b.rfind(b"aa"), Some(2));
+    assert_eq!(b.find(b""), Some(0));
+    assert_eq!(b.rfind(b""), Some(4));
+    assert_eq!(b.find(b"aaaaa"), None);
+    assert_eq!(b.rfind(b"aaaaa"), None);
+    assert!(b.contains(b"aaa"));
+    assert!(!b.contains(b"b"));
+}
+
+#[test]
+fn test_read_ints() {
+    let mut b = Bytes::from(vec![0x01u8, 0x02, 0x01, 0x02, 0xff, 0xff, 0xff, 0xff, 0x07]);
+    assert_eq!(b.read_u16_le(), Some(0x0201));
+    assert_eq!(b.read_u16_be(), Some(0x0102));
+    assert_eq!(b.read_i32_be(), Some(-1));
+    assert_eq!(b.read_u64_le(), None);
+    assert_eq!(b, [0x07]);
+    assert_eq!(b.read_u16_be(), None);
+    assert_eq!(b, [0x07]);
+}
+
+#[test]
+fn test_varint_edge_cases() {
+    let mut b = Bytes::from(vec![0x80u8, 0x80]);
+    assert_eq!(b.read_varint_u64(), None);
+    assert_eq!(b.len(), 2);
+
+    let mut v = Vec::new();
+    encode_uleb128(u64::MAX, &mut v);
+    let mut b = Bytes::from(v);
+    assert_eq!(b.read_varint_u64(), Some(u64::MAX));
+
+    let mut b = Bytes::from(vec![0xffu8, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]);
+    assert_eq!(b.read_varint_u64(), None);
+    assert_eq!(b.len(), 10);
+
+    let mut b = Bytes::from(vec![0x80u8; 11]);
+    assert_eq!(b.read_varint_u64(), None);
+
+    let mut v = Vec::new();
+    encode_sleb128(i64::MIN, &mut v);
+    encode_sleb128(i64::MAX, &mut v);
+    let mut b = Bytes::from(v);
+    assert_eq!(b.read_varint_i64(), Some(i64::MIN));
+    assert_eq!(b.read_varint_i64(), Some(i64::MAX));
+}
+
+#[test]
+fn test_copy_to_slice() {
+    let b = Bytes::from(b"abc".to_vec());
+    let mut dst = [0u8; 4];
+    assert_eq!(b.copy_to_slice(&mut dst), Ok(()));
+    assert_eq!(&dst, b"abc\0");
+    assert_eq!(
+        b.copy_to_slice(&mut dst[..2]),
+        Err(CopyError {
+            src_len: 3,
+            dst_len: 2
+        })
+    );
+    assert_eq!(
+        b.copy_to_slice_exact(&mut dst),
+        Err(CopyError {
+            src_len: 3,
+            dst_len: 4
+        })
+    );
+    let mut dst = [0u8; 3];
+    assert_eq!(b.copy_to_slice_exact(&mut dst), Ok(()));
+    assert_eq!(&dst, b"abc");
+}
+
+#[test]
+fn test_take_frame_be32() {
+    let mut b = Bytes::from(b"\0\0\0\x03abc\0\0\0\0\0\0\0\x05ab".to_vec());
+    assert_eq!(b.take_frame_be32().unwrap(), b"abc");
+    assert_eq!(b.take_frame_be32().unwrap(), b"");
+    let before = b.clone();
+    assert!(b.take_frame_be32().is_none());
+    assert_eq!(b, before);
+
+    let mut b = Bytes::from(b"\0\0".to_vec());
+    assert!(b.take_frame_be32().is_none());
+    assert_eq!(b.len(), 2);
+}
+
+#[test]
+fn test_validate_invariants() {
+    let b = Bytes::from(b"abcd".to_vec());
+    b.validate_invariants();
+    b.slice(1..3).validate_invariants();
+    b.slice(4..).validate_invariants();
+}
+
+#[test]
+#[cfg(any(debug_assertions, feature = "validate"))]
+#[should_panic(expected = "outside of its owner's memory")]
+fn test_validate_invariants_detects_foreign_data() {
+    static OTHER: [u8; 4] = [1, 2, 3, 4];
+    let mut b = Bytes::from(b"abcd".to_vec());
+    b.data = &OTHER;
+    b.validate_invariants();
+}
+
+#[test]
+fn test_partial_eq() {
+    let b = Bytes::from(b"abc".to_vec());
+    let slice: &[u8] = b"abc";
+    let vec = b"abc".to_vec();
+
+    assert!(b == *slice);
+    assert!(*slice == b);
+    assert!(b == slice);
+    assert!(slice == b);
+    assert!(b == vec);
+    assert!(vec == b);
+    assert!(b == *"abc");
+    assert!(*"abc" == b);
+    assert!(b == "abc");
+    assert!("abc" == b);
+    assert!(b == b.clone());
+    assert!(b != "abd");
+    assert!("ab" != b);
+}
+
+#[test]
+fn test_try_unwrap_owner() {
+    let b = Bytes::from(b"abcd".to_vec());
+    let b = b.try_unwrap_owner::<String>().unwrap_err();
+    let c = b.clone();
+    let b = b.try_unwrap_owner::<Vec<u8>>().unwrap_err();
+    drop(c);
+    assert_eq!(b.try_unwrap_owner::<Vec<u8>>().unwrap(), b"abcd");
+}
+
+#[test]
+fn test_into_vec() {
+    let v = b"abcd".to_vec();
+    let ptr = v.as_ptr();
+    let b = Bytes::from(v);
+    let v = b.into_vec();
+    assert_eq!(v, b"abcd");
+    assert_eq!(v.as_ptr(), ptr);
+
+    let b = Bytes::from(v);
+    let sliced = b.slice(1..);
+    drop(b);
+    let v = sliced.into_vec();
+    assert_eq!(v, b"bcd");
+    assert_ne!(v.as_ptr(), ptr.wrapping_add(1));
+
+    let b = Bytes::from(b"abcd".to_vec());
+    let shared = b.clone();
+    let v = b.into_vec();
+    assert_eq!(v, b"abcd");
+    assert_ne!(v.as_ptr(), shared.as_ptr());
+}
+
+#[test]
+fn test_as_str() {
+    let b = Bytes::from(b"hello".to_vec());
+    assert_eq!(b.as_str(), Ok("hello"));
+    let b = Bytes::from(b"\xc3\x28".to_vec());
+    assert!(b.as_str().is_err());
+    assert_eq!(Bytes::empty().as_str(), Ok(""));
+}
+
+#[test]
+fn test_ptr_eq() {
+    let a = Bytes::from(b"abcd".to_vec());
+    let b = Bytes::from(b"abcd".to_vec());
+    assert_eq!(a, b);
+    assert!(!a.ptr_eq(&b));
+    assert!(!a.owner_ptr_eq(&b));
+
+    let c = a.clone();
+    assert!(a.ptr_eq(&c));
+    assert!(a.owner_ptr_eq(&c));
+
+    let d = a.slice(1..);
+    assert!(!a.ptr_eq(&d));
+    assert!(a.owner_ptr_eq(&d));
+    assert!(d.ptr_eq(&a.slice(1..)));
+}
+
+#[test]
+fn test_ref_counts() {
+    let a = Bytes::from(b"abcd".to_vec());
+    assert_eq!(a.strong_count(), 1);
+    assert_eq!(a.weak_count(), 0);
+    let b = a.slice(1..);
+    assert_eq!(a.strong_count(), 2);
+    let w = b.downgrade();
+    assert_eq!(a.weak_count(), 1);
+    drop(b);
+    assert_eq!(a.strong_count(), 1);
+    drop(w);
+    assert_eq!(a.weak_count(), 0);
+}
+
+#[test]
+fn test_from_reader() {
+    let b = Bytes::from_reader(std::io::Cursor::new(b"hello world")).unwrap();
+    assert_eq!(b, b"hello world");
+
+    struct Trickle<'a>(&'a [u8]);
+    impl std::io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(3);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+    let b = Bytes::from_reader_with_capacity(Trickle(b"hello world"), 11).unwrap();
+    assert_eq!(b, b"hello world");
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn test_map_and_load_file() {
+    let _lock = MMAP_LOCK.lock().unwrap();
+    let path = temp_path("map_and_load_file");
+    std::fs::write(&path, b"hello world").unwrap();
+
+    let file = std::fs::File::open(&path).unwrap();
+    let mapped = unsafe { Bytes::map_file(&file) }.unwrap();
+    assert_eq!(mapped, b"hello world");
+    assert!(mapped.downcast_to_owner::<memmap2::Mmap>().is_some());
+
+    let loaded = unsafe { Bytes::load_file(&path) }.unwrap();
+    assert_eq!(loaded, b"hello world");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_from_file() {
+    let path = temp_path("from_file");
+    std::fs::write(&path, b"hello world").unwrap();
+    let b = Bytes::from_file(&path).unwrap();
+    assert_eq!(b, b"hello world");
+    std::fs::remove_file(&path).unwrap();
+    assert!(Bytes::from_file(&path).is_err());
+}
+
+#[test]
+fn test_diff_ranges_long() {
+    let a = vec![0u8; 100];
+    let mut b = a.clone();
+    b[3] = 1;
+    b[40..45].fill(2);
+    b[99] = 3;
+    b.push(4);
+    let bytes = Bytes::from(a);
+    assert_eq!(bytes.diff_ranges(&b), [3..4, 40..45, 99..100]);
+    assert!(bytes.diff_ranges(&bytes).is_empty());
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn test_map_path() {
+    let _lock = MMAP_LOCK.lock().unwrap();
+    let path = temp_path("map_path");
+    std::fs::write(&path, b"hello world").unwrap();
+
+    let mapped = unsafe { Bytes::map_path(&path) }.unwrap();
+    assert_eq!(mapped, b"hello world");
+    let region = unsafe { Bytes::map_path_region(&path, 6, 5) }.unwrap();
+    assert_eq!(region, b"world");
+
+    let file = std::fs::File::open(&path).unwrap();
+    let region = unsafe { Bytes::map_file_region(&file, 2, 3) }.unwrap();
+    assert_eq!(region, b"llo");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(feature = "mmap-accounting")]
+fn test_mapped_bytes_total() {
+    let _lock = MMAP_LOCK.lock().unwrap();
+    let path = temp_path("mapped_bytes_total");
+    std::fs::write(&path, [0u8; 1000]).unwrap();
+
+    let before = crate::mapped_bytes_total();
+    let mapped = unsafe { Bytes::map_path(&path) }.unwrap();
+    let region = unsafe { Bytes::map_path_region(&path, 10, 100) }.unwrap();
+    assert_eq!(crate::mapped_bytes_total(), before + 1100);
+    let slice = mapped.slice(..10);
+    drop(mapped);
+    assert_eq!(crate::mapped_bytes_total(), before + 1100);
+    drop(slice);
+    drop(region);
+    assert_eq!(crate::mapped_bytes_total(), before);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_make_owned() {
+    let original = Bytes::from(b"abcd".to_vec()).slice(1..);
+    let weak = original.downgrade();
+    let owned = original.make_owned();
+    assert_eq!(owned, original);
+    assert!(!owned.owner_ptr_eq(&original));
+    drop(original);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(owned, b"bcd");
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn test_decompress_zstd() {
+    let data = b"hello hello hello hello world".repeat(10);
+    let compressed = zstd::stream::encode_all(&data[..], 3).unwrap();
+    let b = Bytes::from(compressed);
+    assert_eq!(b.decompress_zstd().unwrap(), data);
+    assert!(Bytes::from(b"not zstd".to_vec()).decompress_zstd().is_err());
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn test_decompress_gzip() {
+    use std::io::Write;
+
+    let data = b"hello hello hello hello world".repeat(10);
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&data).unwrap();
+    let b = Bytes::from(encoder.finish().unwrap());
+    assert_eq!(b.decompress_gzip().unwrap(), data);
+    assert!(Bytes::from(b"not gzip".to_vec()).decompress_gzip().is_err());
+}
+
+#[test]
+#[cfg(feature = "crc32")]
+fn test_crc32() {
+    assert_eq!(Bytes::empty().crc32(), 0);
+    let b = Bytes::from(b"xx123456789".to_vec());
+    assert_eq!(b.slice(2..).crc32(), 0xcbf43926);
+    assert_eq!(
+        Bytes::from_source(&b"The quick brown fox jumps over the lazy dog"[..]).crc32(),
+        0x414fa339
+    );
+}
+
+#[test]
+#[cfg(feature = "digest")]
+fn test_digest() {
+    let b = Bytes::from(b"xxabc".to_vec()).slice(2..);
+    let hash = b.digest::<sha2::Sha256>();
+    assert_eq!(
+        Bytes::from_source(hash.to_vec()).to_hex(),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn test_as_slice() {
+    let b = Bytes::from(b"abcd".to_vec());
+    let slice: &[u8] = b.as_slice();
+    assert_eq!(slice, b"abcd");
+    assert_eq!(slice.as_ptr(), b.as_ptr());
+}
+
+#[test]
+fn test_from_array() {
+    let b = Bytes::from([1u8, 2, 3]);
+    assert_eq!(b, [1, 2, 3]);
+    b.validate_invariants();
+    let owner = b.downcast_to_owner::<[u8; 3]>().unwrap();
+    assert_eq!(*owner, [1, 2, 3]);
+
+    let b = Bytes::from([0u8; 0]);
+    assert!(b.is_empty());
+    assert!(b.downcast_to_owner::<[u8; 0]>().is_some());
+
+    let b = Bytes::from([7u8; 64]);
+    assert_eq!(b, [7u8; 64]);
+    assert!(b.downcast_to_owner::<[u8; 64]>().is_some());
+}
+
+#[test]
+#[cfg(feature = "zerocopy")]
+fn test_from_typed_array() {
+    let b = Bytes::from([1u16, 2]);
+    assert_eq!(b.len(), 4);
+    assert!(b.downcast_to_owner::<[u16; 2]>().is_some());
+}
+
+#[test]
+#[cfg(any(unix, windows))]
+fn test_pread() {
+    let path = temp_path("pread");
+    std::fs::write(&path, b"hello world").unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+    assert_eq!(Bytes::pread(&file, 6, 5).unwrap(), b"world");
+    assert_eq!(Bytes::pread(&file, 0, 5).unwrap(), b"hello");
+    assert_eq!(Bytes::pread(&file, 11, 0).unwrap(), b"");
+    let err = Bytes::pread(&file, 8, 5).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(feature = "bytes")]
+fn test_from_bytes_mut() {
+    let mut buf = bytes::BytesMut::with_capacity(16);
+    buf.extend_from_slice(b"hello");
+    buf[0] = b'j';
+    let b = Bytes::from_source(buf);
+    assert_eq!(b, b"jello");
+    let owner = b.downcast_to_owner::<bytes::BytesMut>().unwrap();
+    assert_eq!(&owner[..], b"jello");
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn test_region_alignment() {
+    assert_eq!(Bytes::region_alignment(0), 4096);
+    assert_eq!(Bytes::region_alignment(1), 1);
+    assert_eq!(Bytes::region_alignment(6), 2);
+    assert_eq!(Bytes::region_alignment(24), 8);
+    assert_eq!(Bytes::region_alignment(8192), 4096);
+
+    let _lock = MMAP_LOCK.lock().unwrap();
+    let path = temp_path("region_alignment");
+    std::fs::write(&path, [0u8; 10000]).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+    for offset in [0u64, 1, 6, 24, 4100, 8192] {
+        let region = unsafe { Bytes::map_file_region(&file, offset, 16) }.unwrap();
+        let align = Bytes::region_alignment(offset);
+        assert_eq!(region.as_ptr() as usize % align, 0);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(all(feature = "mmap", unix))]
+fn test_resident_pages() {
+    let _lock = MMAP_LOCK.lock().unwrap();
+    let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    let path = temp_path("resident_pages");
+    std::fs::write(&path, vec![1u8; 3 * page]).unwrap();
+    let mapped = unsafe { Bytes::map_path(&path) }.unwrap();
+    assert!(mapped.is_mapped());
+    // Touch the data so at least one page is resident.
+    assert_eq!(mapped[0], 1);
+    let (resident, total) = mapped.resident_pages().unwrap();
+    assert_eq!(total, 3);
+    assert!(resident >= 1 && resident <= total);
+    let (_, total) = mapped.slice(page - 10..page + 10).resident_pages().unwrap();
+    assert_eq!(total, 2);
+    assert_eq!(mapped.slice(..0).resident_pages().unwrap(), (0, 0));
+
+    let heap = Bytes::from(vec![0u8; 16]);
+    assert!(!heap.is_mapped());
+    let err = heap.resident_pages().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(all(feature = "mmap", unix))]
+fn test_advise() {
+    use crate::Advice;
+
+    let _lock = MMAP_LOCK.lock().unwrap();
+    let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    let path = temp_path("advise");
+    std::fs::write(&path, vec![1u8; 3 * page]).unwrap();
+    let mapped = unsafe { Bytes::map_path(&path) }.unwrap();
+    mapped.advise(Advice::Sequential).unwrap();
+    mapped
+        .slice(page + page / 4..2 * page + page / 4)
+        .advise(Advice::WillNeed)
+        .unwrap();
+    mapped.slice(page..).advise(Advice::DontNeed).unwrap();
+    mapped.slice(..0).advise(Advice::Random).unwrap();
+    assert_eq!(mapped[3 * page - 1], 1);
+
+    Bytes::from(vec![0u8; 16]).advise(Advice::WillNeed).unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(all(feature = "mmap", unix))]
+fn test_lock() {
+    let _lock = MMAP_LOCK.lock().unwrap();
+    let path = temp_path("lock");
+    std::fs::write(&path, vec![1u8; 2 * 4096]).unwrap();
+    let mapped = unsafe { Bytes::map_path(&path) }.unwrap();
+    let key = mapped.slice(100..132);
+    key.lock().unwrap();
+    key.unlock().unwrap();
+    mapped.slice(..0).lock().unwrap();
+
+    // Locks aren't counted per slice, so unlocking one neighbour
+    // unlocks the page it shares with the other.
+    let left = mapped.slice(..10);
+    let right = mapped.slice(10..20);
+    left.lock().unwrap();
+    right.lock().unwrap();
+    #[cfg(target_os = "linux")]
+    {
+        let locked = || -> usize {
+            let status = std::fs::read_to_string("/proc/self/status").unwrap();
+            let line = status.lines().find(|l| l.starts_with("VmLck:")).unwrap();
+            let kb: usize = line.split_whitespace().nth(1).unwrap().parse().unwrap();
+            kb * 1024
+        };
+        let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let before = locked();
+        left.unlock().unwrap();
+        assert_eq!(locked(), before - page);
+    }
+    left.unlock().unwrap();
+    right.unlock().unwrap();
+
+    let err = Bytes::from(vec![0u8; 16]).lock().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(all(feature = "mmap", feature = "zerocopy"))]
+fn test_map_file_as() {
+    use crate::packed::PackError;
+    use crate::{Packed, PackedSlice};
+
+    let _lock = MMAP_LOCK.lock().unwrap();
+    let path = temp_path("map_file_as");
+    let values: Vec<u32> = vec![1, 2, 3, 4];
+    let raw: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+    std::fs::write(&path, &raw).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+
+    let slice: PackedSlice<u32> = unsafe { Bytes::map_file_as(&file) }.unwrap().unwrap();
+    assert_eq!(&*slice, &values[..]);
+    let packed: Result<Packed<u32>, PackError> = unsafe { Bytes::map_file_as(&file) }.unwrap();
+    assert_eq!(packed.err(), Some(PackError::BadLayout));
+    let packed: Packed<[u32; 4]> = unsafe { Bytes::map_file_as(&file) }.unwrap().unwrap();
+    assert_eq!(*packed, [1, 2, 3, 4]);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_slice_borrowed() {
+    let b = Bytes::from(b"abcd".to_vec());
+    let s = b.slice_borrowed(1..3);
+    assert_eq!(s, b"bc");
+    assert_eq!(s.as_ptr(), b[1..].as_ptr());
+    assert_eq!(b.strong_count(), 1);
+}
+
+#[test]
+fn test_into_from_parts() {
+    let b = Bytes::from(b"abcd".to_vec()).slice(1..3);
+    let weak = b.downgrade();
+    let (ptr, len, owner) = b.into_parts();
+    assert_eq!(len, 2);
+    let b = unsafe { Bytes::from_parts(ptr, len, owner) };
+    assert_eq!(b, b"bc");
+    assert!(weak.upgrade().unwrap().ptr_eq(&b));
+    drop(b);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_checked_offset_from() {
+    let start = Bytes::from(b"hello world".to_vec());
+    let mut rest = start.clone();
+    rest.take_prefix(6).unwrap();
+    assert_eq!(rest.checked_offset_from(&start), Some(6));
+    assert_eq!(start.checked_offset_from(&start), Some(0));
+    assert_eq!(start.checked_offset_from(&rest), None);
+
+    let unrelated = Bytes::from(b"hello world".to_vec());
+    assert_eq!(unrelated.checked_offset_from(&start), None);
+    assert_eq!(start.checked_offset_from(&unrelated), None);
+}
+
+#[test]
+fn test_weak_bytes_new() {
+    assert!(WeakBytes::new().upgrade().is_none());
+    assert!(WeakBytes::default().upgrade().is_none());
+}
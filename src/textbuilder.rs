@@ -0,0 +1,84 @@
+use std::fmt;
+
+use crate::Bytes;
+#[cfg(feature = "zerocopy")]
+use crate::PackedStr;
+
+/// A [`fmt::Write`] sink for assembling UTF-8 text that is then
+/// handed out as [Bytes] or [PackedStr] without copying.
+///
+/// # Examples
+///
+/// ```
+/// use anybytes::BytesTextBuilder;
+/// use std::fmt::Write;
+/// let mut builder = BytesTextBuilder::new();
+/// write!(builder, "{},{}", 1, 2).unwrap();
+/// assert_eq!(builder.into_bytes(), b"1,2");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct BytesTextBuilder {
+    text: String,
+}
+
+impl BytesTextBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty builder with room for `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            text: String::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the text written so far.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Converts the builder into `Bytes`.
+    pub fn into_bytes(self) -> Bytes {
+        Bytes::from_source(self.text)
+    }
+
+    /// Converts the builder into a `PackedStr`.
+    ///
+    /// This can't fail because the builder only ever holds valid UTF-8.
+    #[cfg(feature = "zerocopy")]
+    pub fn into_packed_str(self) -> PackedStr {
+        self.text.into()
+    }
+}
+
+impl fmt::Write for BytesTextBuilder {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.text.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.text.push(c);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BytesTextBuilder;
+    use std::fmt::Write;
+
+    #[test]
+    fn write_csv() {
+        let mut builder = BytesTextBuilder::new();
+        for (a, b) in [(1, "x"), (2, "y")] {
+            writeln!(builder, "{a},{b}").unwrap();
+        }
+        assert_eq!(builder.as_str(), "1,x\n2,y\n");
+        assert_eq!(builder.clone().into_bytes(), b"1,x\n2,y\n");
+        #[cfg(feature = "zerocopy")]
+        assert_eq!(&*builder.into_packed_str(), "1,x\n2,y\n");
+    }
+}
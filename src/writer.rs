@@ -0,0 +1,147 @@
+use std::io;
+
+use crate::Bytes;
+
+/// An [`io::Write`] sink that collects everything written into [Bytes].
+///
+/// # Examples
+///
+/// ```
+/// use anybytes::BytesWriter;
+/// let mut reader: &[u8] = b"hello world";
+/// let mut writer = BytesWriter::new();
+/// std::io::copy(&mut reader, &mut writer).unwrap();
+/// assert_eq!(writer.finish(), b"hello world");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct BytesWriter {
+    buf: Vec<u8>,
+}
+
+impl BytesWriter {
+    /// Creates an empty writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty writer with room for `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns `true` if nothing has been written yet.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Converts everything written so far into `Bytes`.
+    pub fn finish(self) -> Bytes {
+        Bytes::from_source(self.buf)
+    }
+}
+
+impl io::Write for BytesWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.buf.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes all `parts` to `writer` using vectored writes.
+///
+/// Partial writes are resumed where they stopped, so this issues far
+/// fewer calls than writing each part on its own.
+pub fn write_all_vectored<W: io::Write + ?Sized>(
+    writer: &mut W,
+    parts: &[Bytes],
+) -> io::Result<()> {
+    let mut slices: Vec<io::IoSlice<'_>> = parts.iter().map(Bytes::as_io_slice).collect();
+    let mut slices = &mut slices[..];
+    io::IoSlice::advance_slices(&mut slices, 0);
+    while !slices.is_empty() {
+        match writer.write_vectored(slices) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ))
+            }
+            Ok(n) => io::IoSlice::advance_slices(&mut slices, n),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+
+    use super::{write_all_vectored, BytesWriter};
+    use crate::Bytes;
+
+    /// Accepts at most three bytes per call.
+    struct Trickle(Vec<u8>);
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let n = buf.len().min(3);
+            self.0.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn vectored() {
+        let parts = [
+            Bytes::from(b"hello".to_vec()),
+            Bytes::empty(),
+            Bytes::from(b" big".to_vec()),
+            Bytes::from(b" world".to_vec()).slice(..),
+        ];
+        let mut sink = Vec::new();
+        write_all_vectored(&mut sink, &parts).unwrap();
+        assert_eq!(sink, b"hello big world");
+
+        let mut sink = Trickle(Vec::new());
+        write_all_vectored(&mut sink, &parts).unwrap();
+        assert_eq!(sink.0, b"hello big world");
+
+        let mut full = [0u8; 4];
+        let err = write_all_vectored(&mut &mut full[..], &parts).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn copy_and_write() {
+        let mut writer = BytesWriter::with_capacity(16);
+        let mut reader: &[u8] = b"hello ";
+        assert_eq!(io::copy(&mut reader, &mut writer).unwrap(), 6);
+        let name = "world";
+        write!(writer, "{name}").unwrap();
+        assert_eq!(writer.len(), 11);
+        assert_eq!(writer.finish(), b"hello world");
+        assert!(BytesWriter::new().finish().is_empty());
+    }
+}
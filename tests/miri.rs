@@ -0,0 +1,159 @@
+/*
+ * Copyright (c) Jan-Paul Bultmann
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Small, allocation-light tests for the unsafe lifetime erasure and
+//! weak pointer reconstruction, meant to be run under Miri:
+//!
+//! ```sh
+//! cargo +nightly miri test --no-default-features --features zerocopy --test miri
+//! ```
+//!
+//! They also run as part of the normal test suite.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anybytes::{ByteSource, Bytes};
+
+/// A heap owner that records when it gets dropped.
+struct Tracked {
+    data: Box<[u8]>,
+    dropped: Arc<AtomicBool>,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        // Touch the data so Miri flags any drop before the last reader.
+        assert!(self.data.iter().all(|&b| b < 0xff));
+        self.dropped.store(true, Ordering::SeqCst);
+    }
+}
+
+unsafe impl ByteSource for Tracked {
+    type Owner = Self;
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn as_owner(self) -> Self::Owner {
+        self
+    }
+}
+
+fn tracked(data: &[u8]) -> (Bytes, Arc<AtomicBool>) {
+    let dropped = Arc::new(AtomicBool::new(false));
+    let owner = Tracked {
+        data: data.into(),
+        dropped: dropped.clone(),
+    };
+    (Bytes::from_source(owner), dropped)
+}
+
+#[test]
+fn slice_outlives_parent() {
+    let (bytes, dropped) = tracked(b"hello world");
+    let world = bytes.slice(6..);
+    drop(bytes);
+    assert!(!dropped.load(Ordering::SeqCst));
+    assert_eq!(world.as_ref(), b"world");
+    drop(world);
+    assert!(dropped.load(Ordering::SeqCst));
+}
+
+#[test]
+fn slice_to_bytes_outlives_parent() {
+    let (bytes, dropped) = tracked(b"hello world");
+    let sub = bytes.slice_to_bytes(&bytes.as_ref()[2..5]).unwrap();
+    drop(bytes);
+    assert_eq!(sub.as_ref(), b"llo");
+    drop(sub);
+    assert!(dropped.load(Ordering::SeqCst));
+}
+
+#[test]
+fn take_prefix_keeps_both_halves_alive() {
+    let (mut bytes, dropped) = tracked(b"abcdef");
+    let prefix = bytes.take_prefix(2).unwrap();
+    assert_eq!(bytes.as_ref(), b"cdef");
+    drop(bytes);
+    assert_eq!(prefix.as_ref(), b"ab");
+    assert!(!dropped.load(Ordering::SeqCst));
+    drop(prefix);
+    assert!(dropped.load(Ordering::SeqCst));
+}
+
+#[test]
+fn weak_upgrade_while_alive() {
+    let (bytes, _dropped) = tracked(b"weak");
+    let sub = bytes.slice(1..3);
+    let weak = sub.downgrade();
+    drop(sub);
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(upgraded.as_ref(), b"ea");
+    drop(bytes);
+    // The upgraded handle alone must keep the owner alive.
+    assert_eq!(upgraded.as_ref(), b"ea");
+}
+
+#[test]
+fn weak_upgrade_after_drop() {
+    let (bytes, dropped) = tracked(b"gone");
+    let weak = bytes.downgrade();
+    drop(bytes);
+    assert!(dropped.load(Ordering::SeqCst));
+    // Must not reconstruct the dangling slice.
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn empty_and_static() {
+    let empty = Bytes::empty();
+    let weak = empty.downgrade();
+    assert_eq!(weak.upgrade().unwrap().as_ref(), b"");
+
+    let s = Bytes::from_source(&b"static"[..]);
+    assert_eq!(s.slice(..0).as_ref(), b"");
+    assert_eq!(s.slice(6..).as_ref(), b"");
+}
+
+#[cfg(feature = "zerocopy")]
+mod packed {
+    use super::*;
+    use anybytes::packed::{Packed, PackedSlice};
+
+    #[test]
+    fn packed_outlives_bytes() {
+        let bytes = Bytes::from(vec![1u32, 2, 3]);
+        let first: Packed<u32> = bytes.slice(..4).try_into().unwrap();
+        let rest: PackedSlice<u32> = bytes.slice(4..).try_into().unwrap();
+        drop(bytes);
+        assert_eq!(*first, 1);
+        assert_eq!(&rest[..], &[2, 3]);
+    }
+
+    #[test]
+    fn packed_prefix_from_tracked() {
+        let (mut bytes, dropped) = tracked(&[0u8; 8]);
+        let head = bytes.packed_prefix::<[u8; 4]>().unwrap();
+        drop(bytes);
+        assert_eq!(*head, [0; 4]);
+        assert!(!dropped.load(Ordering::SeqCst));
+        drop(head);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn packed_or_copy_detaches() {
+        let bytes = Bytes::from(vec![0u64, 5]);
+        let misaligned = bytes.slice(1..9);
+        let expected = u64::from_ne_bytes(misaligned.as_ref().try_into().unwrap());
+        let copied = misaligned.packed_or_copy::<u64>(8).unwrap();
+        drop(bytes);
+        assert_eq!(*copied, expected);
+    }
+}